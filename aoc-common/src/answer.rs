@@ -0,0 +1,190 @@
+use std::{cmp::Ordering, convert::Infallible, fmt, str::FromStr};
+
+use num::{BigInt, ToPrimitive};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A puzzle answer.
+///
+/// Numbers are kept in a canonical form so that equal values compare equal
+/// however they were produced: non-negative values are `Unsigned`, negative
+/// ones `Signed`, and `Big` only holds values that don't fit either.
+/// Build answers with `From`/`parse` rather than the variants to keep it that way.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Big(BigInt),
+    Text(String),
+}
+
+impl From<u64> for Answer {
+    fn from(x: u64) -> Self {
+        Self::Unsigned(x)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(x: i64) -> Self {
+        match u64::try_from(x) {
+            Ok(u) => Self::Unsigned(u),
+            Err(_) => Self::Signed(x),
+        }
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(x: BigInt) -> Self {
+        if let Some(u) = x.to_u64() {
+            Self::Unsigned(u)
+        } else if let Some(i) = x.to_i64() {
+            Self::Signed(i)
+        } else {
+            Self::Big(x)
+        }
+    }
+}
+
+macro_rules! from_via {
+    ($via:ty: $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(x: $t) -> Self {
+                    // usize and isize are at most 64 bits wide
+                    Self::from(x as $via)
+                }
+            }
+        )*
+    };
+}
+
+from_via!(u64: u8, u16, u32, usize);
+from_via!(i64: i8, i16, i32, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::Text(s.to_string())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsigned(x) => write!(f, "{x}"),
+            Self::Signed(x) => write!(f, "{x}"),
+            Self::Big(x) => write!(f, "{x}"),
+            Self::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// The inverse of `Display`: anything that reads as an integer is a number
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.parse::<BigInt>() {
+            Ok(x) => Self::from(x),
+            Err(_) => Self::Text(s.to_string()),
+        })
+    }
+}
+
+impl Answer {
+    /// The answer as a number, if it is one
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Self::Unsigned(x) => Some(BigInt::from(*x)),
+            Self::Signed(x) => Some(BigInt::from(*x)),
+            Self::Big(x) => Some(x.clone()),
+            Self::Text(_) => None,
+        }
+    }
+}
+
+/// Numbers are ordered by value; text isn't ordered at all
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            return Some(Ordering::Equal);
+        }
+        Some(self.to_bigint()?.cmp(&other.to_bigint()?))
+    }
+}
+
+/// Numbers that fit in 64 bits are written as JSON-style numbers, everything else as a string
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Unsigned(x) => serializer.serialize_u64(*x),
+            Self::Signed(x) => serializer.serialize_i64(*x),
+            Self::Big(_) | Self::Text(_) => serializer.collect_str(self),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = Answer;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an integer or a string")
+            }
+            fn visit_u64<E: de::Error>(self, x: u64) -> Result<Answer, E> {
+                Ok(Answer::from(x))
+            }
+            fn visit_i64<E: de::Error>(self, x: i64) -> Result<Answer, E> {
+                Ok(Answer::from(x))
+            }
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Answer, E> {
+                Ok(s.parse().unwrap_or_else(|e| match e {}))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn canonical() {
+        assert_eq!(Answer::from(5_isize), Answer::from(5_usize));
+        assert_eq!(Answer::from(-5_i32), Answer::Signed(-5));
+        assert_eq!(Answer::from(BigInt::from(7)), Answer::Unsigned(7));
+        assert_eq!(Answer::from(BigInt::from(-7)), Answer::Signed(-7));
+    }
+
+    #[test]
+    fn round_trip() {
+        for a in [
+            Answer::from(0_u32),
+            Answer::from(-12_isize),
+            Answer::from(BigInt::from(u64::MAX) * 1000),
+            Answer::from("ABCDEF"),
+        ] {
+            assert_eq!(a.to_string().parse::<Answer>().unwrap(), a);
+        }
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(Answer::from(-3_isize) < Answer::from(2_usize));
+        assert!(Answer::from(BigInt::from(u64::MAX) + 1) > Answer::from(u64::MAX));
+        assert_eq!(Answer::from("a").partial_cmp(&Answer::from(1_u32)), None);
+        assert_eq!(
+            Answer::from("a").partial_cmp(&Answer::from("a")),
+            Some(Ordering::Equal)
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! What every day has in common: the shape of a solution, and how to run one.
+
+use std::fs::read_to_string;
+
+use anyhow::Result;
+use clap::Parser;
+
+mod answer;
+mod parse_error;
+pub mod search;
+
+pub use answer::Answer;
+pub use parse_error::ParseError;
+
+/// A day's puzzle: parse the input, then answer both parts from it
+pub trait Solution {
+    /// Day of the month
+    const DAY: u8;
+
+    /// The puzzle input, parsed
+    type Input;
+
+    /// Malformed input should fail with a [`ParseError`] saying where
+    fn parse(infile: &str) -> Result<Self::Input>;
+    fn part_1(input: &Self::Input) -> Result<Answer>;
+    fn part_2(input: &Self::Input) -> Result<Answer>;
+}
+
+#[derive(Parser)]
+pub struct Opts {
+    infile: std::path::PathBuf,
+}
+
+/// The `main` of every day's own binary
+pub fn main<S: Solution>() -> Result<()> {
+    let opts: Opts = clap::Parser::parse();
+
+    let infile = read_to_string(opts.infile)?;
+    let input = S::parse(&infile).inspect_err(report_parse_error)?;
+
+    println!("Part 1:\n{}", S::part_1(&input)?);
+    println!("Part 2:\n{}", S::part_2(&input)?);
+
+    Ok(())
+}
+
+/// Show where in the input a parse went wrong, if that's what went wrong
+pub fn report_parse_error(e: &anyhow::Error) {
+    if let Some(e) = e.downcast_ref::<ParseError>() {
+        eprintln!("{}", e.diagnostic());
+    }
+}
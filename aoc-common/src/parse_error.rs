@@ -0,0 +1,116 @@
+use std::{error::Error, fmt};
+
+/// Where and why a puzzle input failed to parse.
+///
+/// Lines and columns count from 1, and columns count characters, not bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The text we couldn't make sense of (empty if something was missing)
+    pub found: String,
+    /// What we wanted instead
+    pub expected: String,
+    /// The whole of the offending line, for context
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// `found` must be a slice of `infile`, which is how we know where it is;
+    /// use an empty slice (e.g. `&line[line.len()..]`) to point at something missing.
+    pub fn new(infile: &str, found: &str, expected: impl Into<String>) -> Self {
+        let offset = (found.as_ptr() as usize)
+            .checked_sub(infile.as_ptr() as usize)
+            .filter(|&o| o + found.len() <= infile.len())
+            .expect("`found` is not a slice of `infile`");
+        Self::at(infile, offset, found.len(), expected)
+    }
+
+    /// The `len` bytes from byte `offset` of `infile` aren't what we `expected`
+    pub fn at(infile: &str, offset: usize, len: usize, expected: impl Into<String>) -> Self {
+        let line_start = infile[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = infile[offset..]
+            .find('\n')
+            .map_or(infile.len(), |i| offset + i);
+
+        Self {
+            line: infile[..line_start].matches('\n').count() + 1,
+            column: infile[line_start..offset].chars().count() + 1,
+            found: infile[offset..offset + len].to_string(),
+            expected: expected.into(),
+            source_line: infile[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        }
+    }
+
+    /// The error, the offending line, and a caret under the bad text:
+    ///
+    /// ```text
+    /// line 2, column 1: expected a direction (U, D, L or R), found "X"
+    ///   |
+    /// 2 | X 5 (#0dc571)
+    ///   | ^
+    /// ```
+    pub fn diagnostic(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let indent = " ".repeat(self.column - 1);
+        let carets = "^".repeat(self.found.chars().count().max(1));
+
+        format!(
+            "{self}\n{gutter} |\n{number} | {}\n{gutter} | {indent}{carets}",
+            self.source_line
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        match self.found.is_empty() {
+            true => write!(f, ", found nothing"),
+            false => write!(f, ", found {:?}", self.found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INFILE: &str = "R 6 (#70c710)\nX 5 (#0dc571)\n";
+
+    #[test]
+    fn position() {
+        let e = ParseError::new(INFILE, &INFILE[14..15], "a direction");
+        assert_eq!((e.line, e.column), (2, 1));
+        assert_eq!(e.found, "X");
+        assert_eq!(e.source_line, "X 5 (#0dc571)");
+
+        let e = ParseError::new(INFILE, &INFILE[13..13], "a direction");
+        assert_eq!((e.line, e.column), (1, 14));
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 14: expected a direction, found nothing"
+        );
+    }
+
+    #[test]
+    fn diagnostic() {
+        let e = ParseError::new(INFILE, &INFILE[16..18], "a number");
+        assert_eq!(
+            e.diagnostic(),
+            "line 2, column 3: expected a number, found \"5 \"
+  |
+2 | X 5 (#0dc571)
+  |   ^^"
+        );
+    }
+}
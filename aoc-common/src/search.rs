@@ -0,0 +1,253 @@
+//! Graph searches over anything that can say where it can go next.
+//!
+//! Every search takes several starting states (any of them will do), and
+//! stops at the first goal it expands.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// A node in a search. `W` is the world: whatever the state needs to look at
+/// to find its successors (e.g. the grid), so it needn't carry a reference.
+pub trait SearchState<W: ?Sized>: Clone + Eq + Hash {
+    type Cost: Copy + Ord + Default + Add<Output = Self::Cost>;
+
+    fn successors(&self, world: &W) -> impl IntoIterator<Item = Self>;
+
+    /// The cost of stepping from here to `next`, one of our successors
+    fn cost(&self, next: &Self, world: &W) -> Self::Cost;
+
+    fn is_goal(&self, world: &W) -> bool;
+}
+
+/// A cheapest route to a goal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<S, C> {
+    /// From a start to the goal, both included
+    pub path: Vec<S>,
+    pub cost: C,
+    /// How many states we took off the queue before reaching the goal
+    pub expanded: usize,
+}
+
+/// Every state we've reached, how cheaply, and from where
+struct Tree<S, C> {
+    states: Vec<S>,
+    ids: HashMap<S, usize>,
+    parent: Vec<Option<usize>>,
+    cost: Vec<C>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy + Ord> Tree<S, C> {
+    fn new() -> Self {
+        Self {
+            states: vec![],
+            ids: HashMap::new(),
+            parent: vec![],
+            cost: vec![],
+        }
+    }
+
+    /// Reach `state` for `cost` via `parent`.
+    /// Gives its id, unless we already knew a way at least as cheap.
+    fn offer(&mut self, state: &S, parent: Option<usize>, cost: C) -> Option<usize> {
+        match self.ids.get(state) {
+            Some(&id) if self.cost[id] <= cost => None,
+            Some(&id) => {
+                self.parent[id] = parent;
+                self.cost[id] = cost;
+                Some(id)
+            }
+            None => {
+                let id = self.states.len();
+                self.ids.insert(state.clone(), id);
+                self.states.push(state.clone());
+                self.parent.push(parent);
+                self.cost.push(cost);
+                Some(id)
+            }
+        }
+    }
+
+    fn path(&self, mut id: usize) -> Vec<S> {
+        let mut path = vec![self.states[id].clone()];
+        while let Some(p) = self.parent[id] {
+            path.push(self.states[p].clone());
+            id = p;
+        }
+        path.reverse();
+        path
+    }
+
+    fn found(&self, id: usize, expanded: usize) -> Found<S, C> {
+        Found {
+            path: self.path(id),
+            cost: self.cost[id],
+            expanded,
+        }
+    }
+}
+
+/// Fewest steps to a goal, ignoring costs (though the path's total cost is reported)
+pub fn bfs<W: ?Sized, S: SearchState<W>>(
+    world: &W,
+    starts: impl IntoIterator<Item = S>,
+) -> Option<Found<S, S::Cost>> {
+    let mut tree = Tree::new();
+    let mut queue: VecDeque<usize> = starts
+        .into_iter()
+        .filter_map(|s| tree.offer(&s, None, S::Cost::default()))
+        .collect();
+    let mut expanded = 0;
+
+    while let Some(id) = queue.pop_front() {
+        expanded += 1;
+        let here = tree.states[id].clone();
+        if here.is_goal(world) {
+            return Some(tree.found(id, expanded));
+        }
+        for next in here.successors(world) {
+            if tree.ids.contains_key(&next) {
+                continue;
+            }
+            let cost = tree.cost[id] + here.cost(&next, world);
+            queue.extend(tree.offer(&next, Some(id), cost));
+        }
+    }
+    None
+}
+
+/// Cheapest path to a goal
+pub fn dijkstra<W: ?Sized, S: SearchState<W>>(
+    world: &W,
+    starts: impl IntoIterator<Item = S>,
+) -> Option<Found<S, S::Cost>> {
+    astar(world, starts, |_| S::Cost::default())
+}
+
+/// Cheapest path to a goal, guided by `heuristic`: a lower bound on the cost
+/// from a state to the nearest goal. If it ever overestimates, the path found
+/// may not be the cheapest.
+pub fn astar<W: ?Sized, S: SearchState<W>>(
+    world: &W,
+    starts: impl IntoIterator<Item = S>,
+    heuristic: impl Fn(&S) -> S::Cost,
+) -> Option<Found<S, S::Cost>> {
+    let mut tree = Tree::new();
+    // min-heap on estimated total cost; ties go to the state found first
+    let mut heap = BinaryHeap::new();
+    for s in starts {
+        if let Some(id) = tree.offer(&s, None, S::Cost::default()) {
+            heap.push(Reverse((heuristic(&s), id)));
+        }
+    }
+    let mut expanded = 0;
+
+    while let Some(Reverse((estimate, id))) = heap.pop() {
+        let here = tree.states[id].clone();
+        // stale: we've found a cheaper way here since this was queued
+        if estimate > tree.cost[id] + heuristic(&here) {
+            continue;
+        }
+        expanded += 1;
+        if here.is_goal(world) {
+            return Some(tree.found(id, expanded));
+        }
+        for next in here.successors(world) {
+            let cost = tree.cost[id] + here.cost(&next, world);
+            if let Some(n) = tree.offer(&next, Some(id), cost) {
+                heap.push(Reverse((cost + heuristic(&next), n)));
+            }
+        }
+    }
+    None
+}
+
+/// How many steps it takes to reach each state we can reach at all
+pub fn flood<W: ?Sized, S: SearchState<W>>(
+    world: &W,
+    starts: impl IntoIterator<Item = S>,
+) -> HashMap<S, usize> {
+    let mut steps: HashMap<S, usize> = HashMap::new();
+    let mut queue = VecDeque::new();
+    for s in starts {
+        if !steps.contains_key(&s) {
+            steps.insert(s.clone(), 0);
+            queue.push_back(s);
+        }
+    }
+
+    while let Some(here) = queue.pop_front() {
+        let n = steps[&here] + 1;
+        for next in here.successors(world) {
+            if !steps.contains_key(&next) {
+                steps.insert(next.clone(), n);
+                queue.push_back(next);
+            }
+        }
+    }
+    steps
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A walk along 0..=goal: +1 costs 1, +3 costs 5, -1 costs 1
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct Walk(u32);
+
+    impl SearchState<u32> for Walk {
+        type Cost = u32;
+
+        fn successors(&self, goal: &u32) -> impl IntoIterator<Item = Walk> {
+            [self.0 + 1, self.0 + 3, self.0.wrapping_sub(1)]
+                .into_iter()
+                .filter(|n| n <= goal)
+                .map(Walk)
+        }
+
+        fn cost(&self, next: &Walk, _: &u32) -> u32 {
+            match next.0.abs_diff(self.0) {
+                3 => 5,
+                _ => 1,
+            }
+        }
+
+        fn is_goal(&self, goal: &u32) -> bool {
+            self.0 == *goal
+        }
+    }
+
+    #[test]
+    fn searches() {
+        let found = bfs(&6, [Walk(0)]).unwrap();
+        assert_eq!(found.path, [0, 3, 6].map(Walk));
+        assert_eq!(found.cost, 10);
+
+        let found = dijkstra(&6, [Walk(0)]).unwrap();
+        assert_eq!(found.path.len(), 7);
+        assert_eq!(found.cost, 6);
+
+        let guided = astar(&6, [Walk(0)], |w| 6 - w.0).unwrap();
+        assert_eq!(guided.cost, 6);
+        assert!(guided.expanded <= found.expanded);
+
+        // several starts: the nearer one wins
+        let found = dijkstra(&6, [Walk(0), Walk(5)]).unwrap();
+        assert_eq!((found.path[0], found.cost), (Walk(5), 1));
+
+        assert_eq!(dijkstra(&6, [Walk(10)]), None);
+    }
+
+    #[test]
+    fn flood_fill() {
+        let steps = flood(&6, [Walk(0)]);
+        assert_eq!(steps.len(), 7);
+        assert_eq!(steps[&Walk(6)], 2);
+        assert_eq!(steps[&Walk(5)], 3);
+    }
+}
@@ -0,0 +1,200 @@
+use anyhow::Result;
+use aoc_common::{Answer, ParseError, Solution};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{digit1, multispace1};
+use nom::combinator::{all_consuming, cut, map, map_res};
+use nom::error::{context, VerboseError, VerboseErrorKind};
+use nom::multi::separated_list1;
+use nom::sequence::{preceded, separated_pair};
+use nom::Finish;
+use std::str::FromStr;
+use strum::EnumString;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = Vec<Game>;
+
+    fn parse(infile: &str) -> Result<Vec<Game>> {
+        Ok(parse_games(infile)?)
+    }
+    fn part_1(input: &Vec<Game>) -> Result<Answer> {
+        part_1(input).map(Answer::from)
+    }
+    fn part_2(input: &Vec<Game>) -> Result<Answer> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+/// Game ID, and each handful shown
+pub type Game = (u32, Vec<Handful>);
+
+type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
+#[derive(Debug, PartialOrd, PartialEq, Default)]
+pub struct Handful {
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+#[derive(Debug, EnumString, Clone)]
+#[strum(serialize_all = "lowercase")]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+fn number(input: &str) -> IResult<'_, u32> {
+    context("a number", map_res(digit1, str::parse))(input)
+}
+
+fn colour(input: &str) -> IResult<'_, Color> {
+    context(
+        "a colour (red, green or blue)",
+        map_res(alt((tag("red"), tag("green"), tag("blue"))), |s: &str| {
+            Color::from_str(s)
+        }),
+    )(input)
+}
+
+impl From<Vec<(u32, Color)>> for Handful {
+    fn from(item: Vec<(u32, Color)>) -> Self {
+        let mut out = Self::default();
+
+        for (k, c) in item {
+            match c {
+                Color::Red => out.red += k,
+                Color::Green => out.green += k,
+                Color::Blue => out.blue += k,
+            }
+        }
+        out
+    }
+}
+
+const LIMITS_1: Handful = Handful {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+fn parse_line(input: &str) -> IResult<'_, Game> {
+    let game_tag = context("\"Game \"", tag("Game "));
+    let game_tot = preceded(game_tag, number);
+    let dice = separated_pair(number, multispace1, colour);
+    // Once we've seen a separator, there's no going back
+    let handful = map(separated_list1(tag(", "), cut(dice)), Handful::from);
+    let set_list = separated_list1(tag("; "), cut(handful));
+    let full_line = separated_pair(game_tot, context("\": \"", tag(": ")), set_list);
+    context(
+        "\", \", \"; \" or the end of the line",
+        all_consuming(full_line),
+    )(input)
+}
+
+/// One game per non-empty line
+fn parse_games(infile: &str) -> Result<Vec<Game>, ParseError> {
+    infile
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| {
+            parse_line(l).finish().map(|(_, game)| game).map_err(|e| {
+                // The innermost context is the most specific thing we were looking for
+                let expected = e
+                    .errors
+                    .iter()
+                    .find_map(|(_, kind)| match kind {
+                        VerboseErrorKind::Context(c) => Some(*c),
+                        _ => None,
+                    })
+                    .unwrap_or("a game");
+                let found = e.errors[0].0;
+                let found = &found[..found.find([' ', ',', ';']).unwrap_or(found.len())];
+                ParseError::new(infile, found, expected)
+            })
+        })
+        .collect()
+}
+
+/// Element-wise LEQ to constant
+fn part_1(games: &[Game]) -> Result<u32> {
+    // Game format
+    // Game (\d+): (((\d) (blue|green|red),?\s?))(; ((\d) (blue|green|red),?\s?))*
+
+    // working top down...
+    // separated pair for the ":"
+    //   left: Game N is a tag on "Game "
+    //   right: a separated_list1 of...
+    //     tag(";")
+    //     a separated_list1 of...
+    //       tag(", ")
+    //       separated pair (" ") of:
+    //         left: color
+    //         right: qty
+    let mut total = 0;
+
+    for (id, hands) in games {
+        if hands
+            .iter()
+            .all(|h| h.red <= LIMITS_1.red && h.green <= LIMITS_1.green && h.blue <= LIMITS_1.blue)
+        {
+            total += *id;
+            // print!("[Y] ");
+        } else {
+            // print!("[N] ");
+        }
+        // println!("{id}, {hands:?}");
+    }
+
+    Ok(total)
+}
+
+/// Element-wise min
+fn part_2(games: &[Game]) -> Result<u32> {
+    let mut total = 0;
+    for (_, hands) in games {
+        let min_hand = hands.iter().fold(Handful::default(), |acc, e| Handful {
+            red: acc.red.max(e.red),
+            green: acc.green.max(e.green),
+            blue: acc.blue.max(e.blue),
+        });
+
+        let power = min_hand.red * min_hand.green * min_hand.blue;
+        total += power;
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EG_1: &str = r"
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn test_example_part_1() {
+        assert_eq!(part_1(&parse_games(EG_1).unwrap()).unwrap(), 8);
+    }
+    #[test]
+    fn test_example_part_2() {
+        assert_eq!(part_2(&parse_games(EG_1).unwrap()).unwrap(), 2286);
+    }
+
+    #[test]
+    fn bad_colour() {
+        let e = parse_games("Game 1: 3 blue\nGame 2: 1 blue, 2 purple; 1 red").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 19));
+        assert_eq!(e.found, "purple");
+        assert_eq!(e.expected, "a colour (red, green or blue)");
+    }
+}
@@ -0,0 +1,222 @@
+use anyhow::Result;
+use aoc_common::{Answer, ParseError, Solution};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input = Schematic;
+
+    fn parse(infile: &str) -> Result<Schematic> {
+        parse_schematic(infile)
+    }
+    fn part_1(input: &Schematic) -> Result<Answer> {
+        Ok(part_1(input).into())
+    }
+    fn part_2(input: &Schematic) -> Result<Answer> {
+        Ok(part_2(input).into())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Number {
+    value: usize,
+    x_start: usize,
+    x_end: usize,
+    y: usize,
+}
+
+/// The engine schematic: every number, and every symbol
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Schematic {
+    numbers: Vec<Number>,
+    /// What's at each (x, y) that isn't a digit or `.`
+    symbols: HashMap<(usize, usize), char>,
+}
+
+fn parse_schematic(infile: &str) -> Result<Schematic> {
+    let mut symbols: HashMap<(usize, usize), char> = HashMap::new();
+    let mut numbers: Vec<Number> = Vec::new();
+    let parser = Regex::new(r"([0123456789]+|[^0123456789.])")?;
+
+    for (y, line) in infile.lines().enumerate() {
+        let matches = parser.find_iter(line);
+        for m in matches {
+            let found = m.as_str();
+            if found.starts_with(|c: char| c.is_ascii_digit()) {
+                let value = found
+                    .parse()
+                    .map_err(|_| ParseError::new(infile, found, "a smaller number"))?;
+                numbers.push(Number {
+                    value,
+                    x_start: m.start(),
+                    x_end: m.end(),
+                    y,
+                });
+            } else {
+                symbols.insert((m.start(), y), found.chars().next().expect("one char"));
+            }
+        }
+        // end of line?
+        // symbols.insert((line.len(), y));
+    }
+
+    Ok(Schematic { numbers, symbols })
+}
+
+// 335384 is wrong (deduplicated, ..=x_end)
+// 544664 is *correct* (non deduplicated, ..=x_end)
+fn part_1(schematic: &Schematic) -> usize {
+    let Schematic { numbers, symbols } = schematic;
+
+    // THERE ARE DUPLICATE PART NUMBERS !?!?!?
+
+    let mut potentials: Vec<usize> = numbers
+        .iter()
+        .map(|num| {
+            for x in num.x_start.saturating_sub(1)..=num.x_end {
+                // end is one past already
+                for y in num.y.saturating_sub(1)..=(num.y + 1) {
+                    // println!("({x}, {y})");
+                    if symbols.contains_key(&(x, y)) {
+                        // println!("matched: {num:?} with ({x}, {y})");
+                        return num.value;
+                    }
+                }
+            }
+            // println!("--- no match for {num:?} ---");
+            0
+        })
+        .collect();
+
+    // println!(
+    //     "non-deduplicated count of known part numbers: {}",
+    //     potentials.len()
+    // );
+
+    potentials.sort();
+
+    // println!("{:?}", potentials);
+
+    let potsum: usize = potentials.iter().sum();
+    // println!("non-deduplicated sum: {potsum}");
+
+    // let dedupe: HashSet<usize> = potentials.into_iter().collect();
+
+    // println!(
+    //     "deduped: {} numbers, down from {}",
+    //     dedupe.len(),
+    //     numbers.len()
+    // );
+
+    // dedupe.iter().sum()
+    potsum
+    // I'm so mad that I'm not even gonna delete the code
+}
+fn part_2(schematic: &Schematic) -> usize {
+    let gears: HashSet<(usize, usize)> = schematic
+        .symbols
+        .iter()
+        .filter(|(_, &c)| c == '*')
+        .map(|(&k, _)| k)
+        .collect();
+    let mut numbers: HashMap<(usize, usize), Number> = HashMap::new();
+    for num in &schematic.numbers {
+        for k in num.x_start..num.x_end {
+            numbers.insert((k, num.y), *num);
+        }
+    }
+
+    let numbers = numbers;
+
+    let mut total = 0;
+    for g in gears {
+        let mut adj = HashSet::new();
+        for x in g.0.saturating_sub(1)..=(g.0 + 1) {
+            for y in g.1.saturating_sub(1)..=(g.1 + 1) {
+                if let Some(n) = numbers.get(&(x, y)) {
+                    adj.insert(n);
+                }
+            }
+        }
+        if adj.len() == 2 {
+            // println!("gear: {g:?} with ratios {adj:?}");
+            total += adj.iter().map(|n| n.value).product::<usize>();
+        }
+    }
+
+    total
+}
+
+// numbers: have a line number (y coordinate) and a range (x coordinates)
+// symbols: have an x and a y coordinate
+// if a number has an adjacent symbol with Ysym in +/- 1 and Xsym in +/- 1
+// we add it to total
+// absolute line numbers don't matter so we can use lines().enumerate()
+//
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(&parse_schematic(EXAMPLE_1).unwrap()), 4361);
+    }
+
+    const DUPES_1: &str = r"467..467..
+...*......
+..35..467.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn part_1_dupes() {
+        assert_eq!(
+            part_1(&parse_schematic(DUPES_1).unwrap()),
+            4361 - (633 - 467)
+        );
+    }
+
+    const EOL_1: &str = r"467..114..
+...*......
+..35..6333
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn part_1_eol() {
+        assert_eq!(
+            part_1(&parse_schematic(EOL_1).unwrap()),
+            4361 + (6333 - 633)
+        );
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(&parse_schematic(EXAMPLE_1).unwrap()), 467835);
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use aoc_common::{Answer, ParseError, Solution};
+
+use std::collections::{HashMap, HashSet};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = Vec<Card>;
+
+    fn parse(infile: &str) -> Result<Vec<Card>> {
+        Ok(parse_cards(infile)?)
+    }
+    fn part_1(input: &Vec<Card>) -> Result<Answer> {
+        Ok(part_1(input).into())
+    }
+    fn part_2(input: &Vec<Card>) -> Result<Answer> {
+        Ok(part_2(input).into())
+    }
+}
+
+/// One scratchcard: `Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    winners: HashSet<usize>,
+    numbers: Vec<usize>,
+}
+
+impl Card {
+    /// How many of the numbers we have are winners
+    fn matches(&self) -> usize {
+        self.numbers
+            .iter()
+            .filter(|x| self.winners.contains(x))
+            .count()
+    }
+}
+
+fn parse_cards(infile: &str) -> Result<Vec<Card>, ParseError> {
+    infile
+        .lines()
+        .map(|line| {
+            let missing = |what| ParseError::new(infile, &line[line.len()..], what);
+            let number = |s: &str| {
+                s.parse()
+                    .map_err(|_| ParseError::new(infile, s, "a number"))
+            };
+
+            let (_, rest) = line
+                .split_once(':')
+                .ok_or_else(|| missing("a `:` after the card number"))?;
+            let (wins, cands) = rest
+                .split_once('|')
+                .ok_or_else(|| missing("a `|` between the winning numbers and ours"))?;
+
+            Ok(Card {
+                winners: wins
+                    .split_whitespace()
+                    .map(number)
+                    .collect::<Result<_, _>>()?,
+                numbers: cands
+                    .split_whitespace()
+                    .map(number)
+                    .collect::<Result<_, _>>()?,
+            })
+        })
+        .collect()
+}
+
+fn part_1(cards: &[Card]) -> usize {
+    cards
+        .iter()
+        .map(Card::matches)
+        .filter(|&match_count| match_count > 0)
+        .map(|match_count| 1 << (match_count - 1))
+        .sum()
+}
+fn part_2(cards: &[Card]) -> usize {
+    let mut total: usize = 0;
+
+    // copy id, count
+    let mut copy_counts: HashMap<usize, usize> = HashMap::new();
+
+    for (id, card) in cards.iter().enumerate() {
+        let match_count = card.matches();
+
+        let multiplier: usize = *copy_counts.get(&id).unwrap_or(&1);
+        total += multiplier;
+
+        if match_count > 0 {
+            for k in (id + 1)..=(id + match_count) {
+                *copy_counts.entry(k).or_insert(1) += multiplier;
+            }
+        }
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(&parse_cards(EXAMPLE_1).unwrap()), 13);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(&parse_cards(EXAMPLE_1).unwrap()), 30);
+    }
+
+    #[test]
+    fn bad_cards() {
+        let e = parse_cards("Card 1: 41 48 | 83 86\nCard 2: 13 x2 | 61").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 12, "x2"));
+
+        let e = parse_cards("Card 1: 41 48 83 86").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (1, 20, ""));
+        assert!(parse_cards("Card 1 41 | 83").is_err());
+    }
+}
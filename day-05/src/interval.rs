@@ -0,0 +1,430 @@
+//! Sets of numbers as sorted ranges, and maps which shift some ranges of numbers
+//! about and leave the rest alone.
+
+use std::ops::{Add, AddAssign, Range, Sub, SubAssign};
+
+use super::Lookup;
+
+/// Disjoint, non-adjacent, non-empty ranges in ascending order
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T>(Vec<Range<T>>);
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self(vec![])
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord,
+{
+    /// Sort `ranges`, drop the empty ones, and coalesce any that overlap or touch
+    pub fn normalise(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|r| r.start < r.end);
+        ranges.sort_unstable_by_key(|r| r.start);
+
+        let mut out: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            match out.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => out.push(r),
+            }
+        }
+        Self(out)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalise(self.0.iter().chain(&other.0).cloned().collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (a, b) = (&self.0, &other.0);
+        let mut out = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            let start = a[i].start.max(b[j].start);
+            let end = a[i].end.min(b[j].end);
+            if start < end {
+                out.push(start..end);
+            }
+            match a[i].end < b[j].end {
+                true => i += 1,
+                false => j += 1,
+            }
+        }
+        Self(out)
+    }
+
+    /// What's in `self` but not `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        let b = &other.0;
+        let mut out = vec![];
+        let mut j = 0;
+        for r in &self.0 {
+            // skip what's wholly before this range; the rest might overlap the next one too
+            while j < b.len() && b[j].end <= r.start {
+                j += 1;
+            }
+            let mut start = r.start;
+            for cut in b[j..].iter().take_while(|cut| cut.start < r.end) {
+                if start < cut.start {
+                    out.push(start..cut.start);
+                }
+                start = start.max(cut.end);
+            }
+            if start < r.end {
+                out.push(start..r.end);
+            }
+        }
+        Self(out)
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.0.partition_point(|r| r.end <= x);
+        self.0.get(i).is_some_and(|r| r.start <= x)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The smallest member, if any
+    pub fn min(&self) -> Option<T> {
+        self.0.first().map(|r| r.start)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Range<T>> {
+        self.0.iter()
+    }
+
+    /// The part of the set within `r`
+    pub fn within(&self, r: &Range<T>) -> Self {
+        let first = self.0.partition_point(|s| s.end <= r.start);
+        Self(
+            self.0[first..]
+                .iter()
+                .take_while(|s| s.start < r.end)
+                .map(|s| s.start.max(r.start)..s.end.min(r.end))
+                .collect(),
+        )
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(r: Range<T>) -> Self {
+        Self::normalise(vec![r])
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::normalise(iter.into_iter().collect())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Range<T>;
+    type IntoIter = std::slice::Iter<'a, Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// `source` is shifted so that it starts at `dest`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Piece<T> {
+    pub source: Range<T>,
+    pub dest: T,
+}
+
+impl<T> Piece<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T>,
+{
+    /// `x` must be in `source` (or at its end)
+    fn apply(&self, x: T) -> T {
+        self.dest + (x - self.source.start)
+    }
+
+    /// `y` must be in `dest_range()` (or at its end)
+    fn unapply(&self, y: T) -> T {
+        self.source.start + (y - self.dest)
+    }
+
+    pub fn dest_range(&self) -> Range<T> {
+        self.dest..self.apply(self.source.end)
+    }
+}
+
+/// A function which shifts some ranges of numbers by a fixed offset each, and
+/// leaves everything else where it is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PiecewiseOffsetMap<T> {
+    /// Disjoint, in ascending order of source, and never the identity
+    pieces: Vec<Piece<T>>,
+}
+
+impl<T> Default for PiecewiseOffsetMap<T> {
+    fn default() -> Self {
+        Self { pieces: vec![] }
+    }
+}
+
+impl<T> PiecewiseOffsetMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Pieces must not overlap. Empty and identity pieces are dropped, and
+    /// neighbouring pieces with the same offset joined.
+    pub fn from_pieces(mut pieces: Vec<Piece<T>>) -> Self {
+        pieces.retain(|p| p.source.start < p.source.end && p.dest != p.source.start);
+        pieces.sort_unstable_by_key(|p| p.source.start);
+        debug_assert!(pieces
+            .windows(2)
+            .all(|w| w[0].source.end <= w[1].source.start));
+
+        let mut out: Vec<Piece<T>> = Vec::with_capacity(pieces.len());
+        for p in pieces {
+            match out.last_mut() {
+                Some(last)
+                    if last.source.end == p.source.start
+                        && last.apply(last.source.end) == p.dest =>
+                {
+                    last.source.end = p.source.end
+                }
+                _ => out.push(p),
+            }
+        }
+        Self { pieces: out }
+    }
+
+    /// The map a stage of the almanac describes. Where lookups overlap, the first one wins.
+    pub fn from_lookups(lookups: &[Lookup<T>]) -> Self
+    where
+        T: Default + AddAssign + SubAssign,
+    {
+        let mut covered = IntervalSet::default();
+        let mut pieces = vec![];
+        for l in lookups {
+            let source = IntervalSet::from(l.source..l.source + l.length);
+            for r in source.difference(&covered).iter() {
+                pieces.push(Piece {
+                    source: r.clone(),
+                    dest: l.dest + (r.start - l.source),
+                });
+            }
+            covered = covered.union(&source);
+        }
+        Self::from_pieces(pieces)
+    }
+
+    pub fn pieces(&self) -> &[Piece<T>] {
+        &self.pieces
+    }
+
+    pub fn get(&self, x: T) -> T {
+        let i = self.pieces.partition_point(|p| p.source.end <= x);
+        match self.pieces.get(i) {
+            Some(p) if p.source.start <= x => p.apply(x),
+            _ => x,
+        }
+    }
+
+    /// `r` cut where pieces start and end, with the piece (if any) covering each cut
+    fn segments(&self, r: Range<T>) -> Vec<(Range<T>, Option<&Piece<T>>)> {
+        let mut out = vec![];
+        let mut start = r.start;
+        let first = self.pieces.partition_point(|p| p.source.end <= start);
+        for p in self.pieces[first..]
+            .iter()
+            .take_while(|p| p.source.start < r.end)
+        {
+            if start < p.source.start {
+                out.push((start..p.source.start, None));
+                start = p.source.start;
+            }
+            let end = r.end.min(p.source.end);
+            out.push((start..end, Some(p)));
+            start = end;
+        }
+        if start < r.end {
+            out.push((start..r.end, None));
+        }
+        out
+    }
+
+    /// Where everything in `set` ends up
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.iter()
+            .flat_map(|r| self.segments(r.clone()))
+            .map(|(seg, p)| match p {
+                Some(p) => p.apply(seg.start)..p.apply(seg.end),
+                None => seg,
+            })
+            .collect()
+    }
+
+    /// Where the offset changes: the starts and ends of pieces, in order
+    pub fn breakpoints(&self) -> Vec<T> {
+        let mut out: Vec<T> = self
+            .pieces
+            .iter()
+            .flat_map(|p| [p.source.start, p.source.end])
+            .collect();
+        out.dedup();
+        out
+    }
+
+    /// The smallest value the map takes over `set`.
+    ///
+    /// The map only ever goes up in steps of one between breakpoints, so the
+    /// least value is at the start of one of the ranges, or at a breakpoint.
+    pub fn min_over(&self, set: &IntervalSet<T>) -> Option<T> {
+        let breakpoints = self.breakpoints();
+        set.iter()
+            .flat_map(|r| {
+                let first = breakpoints.partition_point(|&b| b <= r.start);
+                let inside = breakpoints[first..].iter().take_while(|&&b| b < r.end);
+                std::iter::once(&r.start).chain(inside)
+            })
+            .map(|&x| self.get(x))
+            .min()
+    }
+
+    /// Everything the map sends into `set`.
+    ///
+    /// This isn't the inverse of the map, as there might not be one: two
+    /// numbers can end up in the same place, if one is moved onto where the
+    /// other is left alone.
+    pub fn preimage(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let domain: IntervalSet<T> = self.pieces.iter().map(|p| p.source.clone()).collect();
+        // what's left where it is
+        let mut out: Vec<Range<T>> = set.difference(&domain).0;
+        // and what's moved there
+        for p in &self.pieces {
+            for r in set.within(&p.dest_range()).iter() {
+                out.push(p.unapply(r.start)..p.unapply(r.end));
+            }
+        }
+        IntervalSet::normalise(out)
+    }
+
+    /// One map doing `self`, then `next`
+    pub fn then(&self, next: &Self) -> Self {
+        let mut pieces = vec![];
+
+        // where we move things, then wherever `next` moves them on to
+        for p in &self.pieces {
+            for (seg, q) in next.segments(p.dest_range()) {
+                pieces.push(Piece {
+                    source: p.unapply(seg.start)..p.unapply(seg.end),
+                    dest: q.map_or(seg.start, |q| q.apply(seg.start)),
+                });
+            }
+        }
+        // where we leave things be, whatever `next` does
+        for q in &next.pieces {
+            for (seg, p) in self.segments(q.source.clone()) {
+                if p.is_none() {
+                    pieces.push(Piece {
+                        dest: q.apply(seg.start),
+                        source: seg,
+                    });
+                }
+            }
+        }
+
+        Self::from_pieces(pieces)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn set(ranges: &[Range<usize>]) -> IntervalSet<usize> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn interval_set() {
+        let a = set(&[5..8, 0..3, 2..4, 8..9, 12..12]);
+        assert_eq!(a, IntervalSet(vec![0..4, 5..9]));
+        let b = set(&[3..6, 7..20]);
+
+        assert_eq!(a.union(&b), IntervalSet::from(0..20));
+        assert_eq!(a.intersection(&b), set(&[3..4, 5..6, 7..9]));
+        assert_eq!(a.difference(&b), set(&[0..3, 6..7]));
+        assert_eq!(b.difference(&a), set(&[4..5, 9..20]));
+        assert!(a.contains(8) && !a.contains(4) && !a.contains(9));
+        assert_eq!(a.min(), Some(0));
+        assert_eq!(a.iter().count(), 2);
+    }
+
+    #[test]
+    fn offset_map() {
+        // 10..15 -> 20..25, 20..30 -> 0..10
+        let f = PiecewiseOffsetMap::from_pieces(vec![
+            Piece {
+                source: 20..30,
+                dest: 0,
+            },
+            Piece {
+                source: 10..15,
+                dest: 20,
+            },
+        ]);
+        assert_eq!([5, 10, 14, 15, 25].map(|x| f.get(x)), [5, 20, 24, 15, 5]);
+        assert_eq!(
+            f.map_set(&set(&[0..12, 28..32])),
+            // 28..30 lands on 8..10, which 0..10 already covers
+            set(&[0..10, 20..22, 30..32])
+        );
+
+        // f then f: 10..15 -> 20..25 -> 0..5
+        let ff = f.then(&f);
+        for x in 0..40 {
+            assert_eq!(ff.get(x), f.get(f.get(x)), "{x}");
+        }
+        assert_eq!(
+            ff.pieces()[0],
+            Piece {
+                source: 10..15,
+                dest: 0
+            }
+        );
+    }
+
+    #[test]
+    fn inverse_image() {
+        let f = PiecewiseOffsetMap::from_pieces(vec![
+            Piece {
+                source: 20..30,
+                dest: 0,
+            },
+            Piece {
+                source: 10..15,
+                dest: 20,
+            },
+        ]);
+        assert_eq!(f.breakpoints(), [10, 15, 20, 30]);
+
+        // 3 is where 3 and 23 go; 20..22 is where 10..12 goes, and 20..30 itself goes elsewhere
+        let targets = set(&[3..4, 20..22]);
+        assert_eq!(f.preimage(&targets), set(&[3..4, 10..12, 23..24]));
+        for x in 0..40 {
+            assert_eq!(
+                f.preimage(&targets).contains(x),
+                targets.contains(f.get(x)),
+                "{x}"
+            );
+        }
+
+        assert_eq!(f.min_over(&IntervalSet::from(12..28)), Some(0));
+        assert_eq!(f.min_over(&IntervalSet::from(12..18)), Some(15));
+        assert_eq!(f.min_over(&set(&[])), None);
+        assert_eq!(set(&[0..5, 8..12]).within(&(3..9)), set(&[3..5, 8..9]));
+    }
+}
@@ -0,0 +1,534 @@
+use anyhow::{Context, Result};
+use aoc_common::{search::bfs, search::SearchState, Answer, ParseError, Solution};
+use itertools::Itertools;
+
+#[cfg(test)]
+use std::ops::Range;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+mod interval;
+#[cfg(test)]
+mod proptests;
+
+pub use interval::{IntervalSet, Piece, PiecewiseOffsetMap};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = Config<usize>;
+
+    fn parse(infile: &str) -> Result<Config<usize>> {
+        Ok(config_scraper(infile)?)
+    }
+    fn part_1(input: &Config<usize>) -> Result<Answer> {
+        part_1(input).map(Answer::from)
+    }
+    fn part_2(input: &Config<usize>) -> Result<Answer> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+fn part_1(config: &Config<usize>) -> Result<usize> {
+    let chain = config.chain("seed", "location")?;
+    let minimum = config
+        .seeds
+        .iter()
+        .map(|&x| chain.iter().fold(x, |x, map| map.get(x)))
+        .min()
+        .context(":shrug:")?;
+
+    Ok(minimum)
+}
+fn part_2(config: &Config<usize>) -> Result<usize> {
+    // sike, we actually have an infeasible-to-brute force number of seeds to consider
+    // config.seeds is in (initial, range) pairs now
+
+    // A Lookup is really saying that a certain input range has a certain (non-zero) offset
+    // Otherwise the offset is zero
+
+    // so the whole almanac is one map made of offsets, and between the places the offset
+    // changes, the lowest seed gives the lowest location
+
+    config
+        .seed_to_location()?
+        .min_over(&config.seed_ranges())
+        .context("no seeds")
+}
+
+#[derive(Debug, Default, PartialOrd, PartialEq, Copy, Clone)]
+pub struct Lookup<T>
+where
+    T: AddAssign
+        + SubAssign
+        + Ord
+        + Eq
+        + Copy
+        + Sub<Output = T>
+        + Add<Output = T>
+        + std::default::Default,
+{
+    source: T,
+    dest: T,
+    length: T,
+}
+
+/// The almanac: the seeds, and the maps between categories in the order given
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Config<T>
+where
+    T: AddAssign + SubAssign + Ord + Eq + Copy + Sub<Output = T> + Add<Output = T> + Default,
+{
+    seeds: Vec<usize>,
+    maps: Vec<CategoryMap<T>>,
+}
+
+/// The lookups under one "X-to-Y map:" header
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct CategoryMap<T>
+where
+    T: AddAssign + SubAssign + Ord + Eq + Copy + Sub<Output = T> + Add<Output = T> + Default,
+{
+    pub from: String,
+    pub to: String,
+    lookups: Vec<Lookup<T>>,
+}
+
+impl<T> CategoryMap<T>
+where
+    T: AddAssign + SubAssign + Ord + Eq + Copy + Sub<Output = T> + Add<Output = T> + Default,
+{
+    /// The first lookup which covers `x` wins; anything not covered stays put
+    pub fn get(&self, x: T) -> T {
+        self.lookups.iter().find_map(|l| l.get(x)).unwrap_or(x)
+    }
+}
+
+/// The category graph, searching for `goal`
+struct Categories<'a, T>
+where
+    T: AddAssign + SubAssign + Ord + Eq + Copy + Sub<Output = T> + Add<Output = T> + Default,
+{
+    maps: &'a [CategoryMap<T>],
+    goal: &'a str,
+}
+
+impl<'a, T> SearchState<Categories<'a, T>> for &'a str
+where
+    T: AddAssign + SubAssign + Ord + Eq + Copy + Sub<Output = T> + Add<Output = T> + Default,
+{
+    type Cost = usize;
+
+    fn successors(&self, world: &Categories<'a, T>) -> impl IntoIterator<Item = &'a str> {
+        let (here, maps) = (*self, world.maps);
+        maps.iter()
+            .filter(move |m| m.from == here)
+            .map(|m| m.to.as_str())
+    }
+
+    fn cost(&self, _: &&'a str, _: &Categories<'a, T>) -> usize {
+        1
+    }
+
+    fn is_goal(&self, world: &Categories<'a, T>) -> bool {
+        *self == world.goal
+    }
+}
+
+impl<T> Config<T>
+where
+    T: AddAssign + SubAssign + Ord + Eq + Copy + Sub<Output = T> + Add<Output = T> + Default,
+{
+    /// Every category named in a header, in order of first appearance
+    pub fn categories(&self) -> Vec<&str> {
+        self.maps
+            .iter()
+            .flat_map(|m| [m.from.as_str(), m.to.as_str()])
+            .unique()
+            .collect()
+    }
+
+    /// The maps which turn a `from` into a `to`, in the order they apply.
+    /// Where there's more than one way, we take the fewest maps.
+    pub fn chain(&self, from: &str, to: &str) -> Result<Vec<&CategoryMap<T>>> {
+        let world = Categories {
+            maps: &self.maps,
+            goal: to,
+        };
+        let found = bfs(&world, [from]).with_context(|| {
+            format!(
+                "no way to turn {from} into {to} (categories: {})",
+                self.categories().join(", ")
+            )
+        })?;
+        Ok(found
+            .path
+            .iter()
+            .tuple_windows()
+            .filter_map(|(a, b)| self.maps.iter().find(|m| m.from == *a && m.to == *b))
+            .collect())
+    }
+
+    /// Everything which turns a `from` into a `to`, as one map
+    pub fn converter(&self, from: &str, to: &str) -> Result<PiecewiseOffsetMap<T>> {
+        Ok(self
+            .chain(from, to)?
+            .into_iter()
+            .map(|m| PiecewiseOffsetMap::from_lookups(&m.lookups))
+            .fold(PiecewiseOffsetMap::default(), |acc, stage| acc.then(&stage)))
+    }
+
+    /// The whole almanac as one map
+    pub fn seed_to_location(&self) -> Result<PiecewiseOffsetMap<T>> {
+        self.converter("seed", "location")
+    }
+}
+
+impl Config<usize> {
+    /// The seeds as (start, length) pairs, as in part 2
+    pub fn seed_ranges(&self) -> IntervalSet<usize> {
+        self.seeds
+            .iter()
+            .tuples()
+            .map(|(base, len)| (*base)..(*base + *len))
+            .collect()
+    }
+}
+
+impl<T> Lookup<T>
+where
+    T: Default + AddAssign + SubAssign + Ord + Eq + Copy + Sub<Output = T> + Add<Output = T>,
+{
+    fn get(&self, input: T) -> Option<T> {
+        if input >= self.source && input - self.source < self.length {
+            let offset = input - self.source;
+            return Some(self.dest + offset);
+        }
+        None
+    }
+
+    #[cfg(test)]
+    fn get_range(&self, input: &Range<T>) -> Partition<T> {
+        let source_range = self.source..(self.source + self.length);
+
+        let (before, overlap, after) = range_partition::<T>(input, &source_range);
+
+        if let Some(o) = overlap {
+            // this is the part which gets offset
+            // we're working with usizes here which means no negative offsets
+
+            let tx = if self.source > self.dest {
+                let offset = self.source - self.dest;
+                (o.start - offset)..(o.end - offset)
+            } else {
+                let offset = self.dest - self.source;
+                (o.start + offset)..(o.end + offset)
+            };
+
+            return (before, Some(tx), after);
+        }
+
+        (before, overlap, after)
+    }
+}
+/// (before, overlap, after)
+#[cfg(test)]
+type Partition<T> = (Option<Range<T>>, Option<Range<T>>, Option<Range<T>>);
+
+/// partition `base` by `part`
+/// (before, overlap, after)
+/// `.0`: the section of `base` < `part`
+/// `.1`: the overlap
+/// `.2`: the section of `base` > `part`
+#[cfg(test)]
+fn range_partition<T>(base: &Range<T>, part: &Range<T>) -> Partition<T>
+where
+    T: Default + AddAssign + SubAssign + Ord + Eq + Copy + Sub<Output = T> + Add<Output = T>,
+{
+    let mut before = None;
+    let mut during = None;
+    let mut after = None;
+
+    if base.start < part.start {
+        // we have a before
+        before = Some(base.start..base.end.min(part.start));
+        if part.start < base.end && base.end <= part.end {
+            // we also have a during
+            during = Some(part.start..base.end)
+        }
+        if part.end < base.end {
+            // we have a before, during and after
+            during = Some(part.clone());
+            after = Some(part.end..base.end);
+        }
+    } else if part.contains(&base.start) {
+        // we have an no before, but we do have a during
+        during = Some(base.start..base.end.min(part.end));
+        if part.end < base.end {
+            // we also have an after
+            after = Some(part.end..base.end);
+        }
+    } else {
+        // we only have an after
+        after = Some(base.clone());
+    }
+
+    (before, during, after)
+}
+
+#[cfg(test)]
+fn multi_range<T>(lookups: &[Lookup<T>], ranges: &[Range<T>]) -> Vec<Range<T>>
+where
+    T: Default
+        + AddAssign
+        + SubAssign
+        + Ord
+        + Eq
+        + Copy
+        + Sub<Output = T>
+        + Add<Output = T>
+        + std::fmt::Debug,
+{
+    let mut out = vec![];
+    let mut todo = vec![];
+    for r in ranges {
+        todo.push(r.clone());
+    }
+
+    for lookup in lookups {
+        let mut new = vec![];
+        for r in &todo {
+            let (b, o, a) = lookup.get_range(r);
+            // println!("{lookup:?} of {r:?} -> ({b:?} ; {o:?} ; {a:?})");
+            if let Some(before) = b {
+                new.push(before);
+            }
+            if let Some(overlap) = o {
+                out.push(overlap);
+                // must be converted at most once
+            }
+            if let Some(after) = a {
+                new.push(after);
+            }
+        }
+        // println!("after {lookup:?}, converted {out:?}, still todo {new:?}");
+        todo = new;
+    }
+
+    out.extend_from_slice(&todo);
+    out
+}
+
+fn config_scraper(infile: &str) -> Result<Config<usize>, ParseError> {
+    // a `seeds:` line, then each map is an "X-to-Y map:" header and one lookup per line
+    let mut seeds = None;
+    let mut maps: Vec<CategoryMap<usize>> = vec![];
+
+    for line in infile.lines().filter(|l| !l.trim().is_empty()) {
+        if let Some(rest) = line.strip_prefix("seeds:") {
+            seeds = Some(numbers(infile, rest)?);
+        } else if let Some(header) = line.trim_end().strip_suffix(" map:") {
+            let (from, to) = header.split_once("-to-").ok_or_else(|| {
+                ParseError::new(infile, line, "a header like `soil-to-water map:`")
+            })?;
+            maps.push(CategoryMap {
+                from: from.trim().to_string(),
+                to: to.to_string(),
+                lookups: vec![],
+            });
+        } else {
+            match (maps.last_mut(), &mut seeds) {
+                (Some(map), _) => map.lookups.push(lookup(infile, line)?),
+                (None, Some(seeds)) => seeds.extend(numbers(infile, line)?),
+                (None, None) => return Err(ParseError::new(infile, line, "a `seeds:` line")),
+            }
+        }
+    }
+
+    let seeds = seeds.ok_or_else(|| {
+        let end = &infile[infile.len()..];
+        ParseError::new(infile, end, "a `seeds:` line")
+    })?;
+    Ok(Config { seeds, maps })
+}
+
+/// Every whitespace-separated word of `section`, as a number
+fn numbers(infile: &str, section: &str) -> Result<Vec<usize>, ParseError> {
+    section
+        .split_whitespace()
+        .map(|s| {
+            s.parse()
+                .map_err(|_| ParseError::new(infile, s, "a number"))
+        })
+        .collect()
+}
+
+/// A `Lookup` from its line: destination, source and length
+fn lookup(infile: &str, line: &str) -> Result<Lookup<usize>, ParseError> {
+    match numbers(infile, line)?[..] {
+        [dest, source, length] => Ok(Lookup {
+            source,
+            dest,
+            length,
+        }),
+        _ => Err(ParseError::new(
+            infile,
+            line,
+            "three numbers (destination, source and length)",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r"seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(&config_scraper(EXAMPLE_1).unwrap()).unwrap(), 35);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(&config_scraper(EXAMPLE_1).unwrap()).unwrap(), 46);
+    }
+
+    #[test]
+    fn bad_lookup() {
+        let infile = EXAMPLE_1.replace("37 52 2", "37 52");
+        let e = config_scraper(&infile).unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (9, 1, "37 52"));
+    }
+
+    #[test]
+    fn composed_stages() {
+        let config = config_scraper(EXAMPLE_1).unwrap();
+        let stages: Vec<_> = config
+            .chain("seed", "location")
+            .unwrap()
+            .iter()
+            .map(|m| PiecewiseOffsetMap::from_lookups(&m.lookups))
+            .collect();
+        assert_eq!(stages.len(), 7);
+
+        let composed = config.seed_to_location().unwrap();
+        for x in 0..110 {
+            let stepwise = stages.iter().fold(x, |x, s| s.get(x));
+            assert_eq!(composed.get(x), stepwise, "seed {x}");
+        }
+        assert_eq!([79, 14, 55, 13].map(|x| composed.get(x)), [82, 43, 86, 35]);
+    }
+
+    #[test]
+    fn seeds_for_locations() {
+        let config = config_scraper(EXAMPLE_1).unwrap();
+        let composed = config.seed_to_location().unwrap();
+
+        let seeds = composed.preimage(&IntervalSet::from(46..47));
+        assert!(seeds.contains(82));
+        for x in 0..110 {
+            assert_eq!(seeds.contains(x), composed.get(x) == 46, "seed {x}");
+        }
+    }
+
+    #[test]
+    fn category_graph() {
+        // the maps in reverse order, with a branch off to colours on the way
+        let (seeds, maps) = EXAMPLE_1.split_once("\n\n").unwrap();
+        let mut sections: Vec<&str> = maps.split("\n\n").collect();
+        sections.reverse();
+        sections.insert(3, "soil-to-colour map:\n0 10 5");
+        let infile = format!("{seeds}\n\n{}", sections.join("\n\n"));
+
+        let config = config_scraper(&infile).unwrap();
+        assert_eq!(part_1(&config).unwrap(), 35);
+        assert_eq!(config.categories().len(), 9);
+
+        let chain = config.chain("soil", "humidity").unwrap();
+        let names: Vec<&str> = chain.iter().map(|m| m.to.as_str()).collect();
+        assert_eq!(
+            names,
+            ["fertilizer", "water", "light", "temperature", "humidity"]
+        );
+
+        let colour = config.converter("seed", "colour").unwrap();
+        assert_eq!([79, 14, 55, 13].map(|x| colour.get(x)), [81, 4, 57, 3]);
+
+        assert!(config.chain("location", "seed").is_err());
+        assert!(config.chain("seed", "hue").is_err());
+        assert!(config.chain("soil", "soil").unwrap().is_empty());
+    }
+
+    #[test]
+    fn bad_header() {
+        let infile = EXAMPLE_1.replace("water-to-light", "water-light");
+        let e = config_scraper(&infile).unwrap_err();
+        assert_eq!((e.line, e.found.as_str()), (18, "water-light map:"));
+
+        assert!(config_scraper("50 98 2\nseeds: 1").is_err());
+    }
+
+    #[test]
+    fn lookup_is_half_open() {
+        let l = Lookup {
+            source: 98,
+            dest: 50,
+            length: 2,
+        };
+        assert_eq!(
+            [97, 98, 99, 100].map(|x| l.get(x)),
+            [None, Some(50), Some(51), None]
+        );
+    }
+
+    #[test]
+    fn test_range_partition() {
+        let a = 0..5;
+        let b = 4..10;
+        let c = 6..8;
+
+        assert_eq!(range_partition(&a, &b), (Some(0..4), Some(4..5), None));
+        assert_eq!(range_partition(&a, &c), (Some(0..5), None, None));
+        assert_eq!(range_partition(&c, &b), (None, Some(6..8), None));
+        assert_eq!(range_partition(&c, &a), (None, None, Some(6..8)));
+        assert_eq!(
+            range_partition(&b, &c),
+            (Some(4..6), Some(6..8), Some(8..10))
+        );
+        assert_eq!(range_partition(&b, &a), (None, Some(4..5), Some(5..10)))
+    }
+}
@@ -0,0 +1,107 @@
+//! Random almanacs, pushed through every way we have of reading them, checked
+//! against the obvious (slow) way: one seed at a time.
+
+use proptest::{collection::vec, prelude::*};
+
+use super::*;
+
+/// Where each seed in `ranges` ends up after `lookups`, one by one
+fn pointwise(lookups: &[Lookup<usize>], ranges: &[Range<usize>]) -> Vec<usize> {
+    ranges
+        .iter()
+        .flat_map(|r| r.clone())
+        .map(|x| lookups.iter().find_map(|l| l.get(x)).unwrap_or(x))
+        .collect()
+}
+
+fn singletons(xs: &[usize]) -> IntervalSet<usize> {
+    xs.iter().map(|&x| x..x + 1).collect()
+}
+
+fn lookup() -> impl Strategy<Value = Lookup<usize>> {
+    (0..100usize, 0..100usize, 0..30usize).prop_map(|(dest, source, length)| Lookup {
+        source,
+        dest,
+        length,
+    })
+}
+
+fn ranges() -> impl Strategy<Value = Vec<Range<usize>>> {
+    vec(
+        (0..120usize, 0..30usize).prop_map(|(start, len)| start..start + len),
+        0..5,
+    )
+}
+
+/// Seeds in (start, length) pairs, and between one and five stages from seed to location
+fn almanac() -> impl Strategy<Value = Config<usize>> {
+    (ranges(), vec(vec(lookup(), 0..6), 1..=5)).prop_map(|(seeds, stages)| {
+        let n = stages.len();
+        let name = |i: usize| match i {
+            0 => "seed".to_string(),
+            i if i == n => "location".to_string(),
+            i => format!("stage{i}"),
+        };
+        Config {
+            seeds: seeds.iter().flat_map(|r| [r.start, r.len()]).collect(),
+            maps: stages
+                .into_iter()
+                .enumerate()
+                .map(|(i, lookups)| CategoryMap {
+                    from: name(i),
+                    to: name(i + 1),
+                    lookups,
+                })
+                .collect(),
+        }
+    })
+}
+
+proptest! {
+    #[test]
+    fn lookup_agrees_with_get_range(l in lookup(), x in 0..150usize) {
+        let (_, overlap, _) = l.get_range(&(x..x + 1));
+        let by_range = overlap.filter(|o| !o.is_empty()).map(|o| o.start);
+        prop_assert_eq!(l.get(x), by_range);
+    }
+
+    #[test]
+    fn multi_range_agrees_with_pointwise(
+        lookups in vec(lookup(), 0..6),
+        ranges in ranges(),
+    ) {
+        let by_range = IntervalSet::normalise(multi_range(&lookups, &ranges));
+        prop_assert_eq!(by_range, singletons(&pointwise(&lookups, &ranges)));
+    }
+
+    #[test]
+    fn offset_map_agrees_with_pointwise(
+        lookups in vec(lookup(), 0..6),
+        ranges in ranges(),
+    ) {
+        let by_map = PiecewiseOffsetMap::from_lookups(&lookups).map_set(&ranges.iter().cloned().collect());
+        prop_assert_eq!(by_map, singletons(&pointwise(&lookups, &ranges)));
+    }
+
+    #[test]
+    fn parts_agree_with_pointwise(config in almanac()) {
+        let chain = config.chain("seed", "location").unwrap();
+        let through = |seeds: &[Range<usize>]| {
+            chain.iter().fold(seeds.to_vec(), |ranges, map| {
+                pointwise(&map.lookups, &ranges).into_iter().map(|x| x..x + 1).collect()
+            })
+        };
+
+        let singles: Vec<_> = config.seeds.iter().map(|&x| x..x + 1).collect();
+        let expected = through(&singles).iter().map(|r| r.start).min();
+        prop_assert_eq!(part_1(&config).ok(), expected);
+
+        let seeds: Vec<_> = config.seed_ranges().iter().cloned().collect();
+        let expected = through(&seeds).iter().map(|r| r.start).min();
+        prop_assert_eq!(part_2(&config).ok(), expected);
+
+        let by_range = chain.iter().fold(seeds.clone(), |ranges, map| multi_range(&map.lookups, &ranges));
+        let by_range = IntervalSet::normalise(by_range);
+        prop_assert_eq!(by_range.min(), expected);
+    }
+}
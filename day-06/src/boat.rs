@@ -0,0 +1,257 @@
+//! Races under other rules: how the boat goes for how long you hold the button.
+//!
+//! Every model here rises to a peak and then falls away as the hold gets
+//! longer, so the holds which beat the record are one run of whole ms. Where
+//! we can solve for its ends directly we do; otherwise we bisect for the peak,
+//! and then for where each side crosses the record.
+
+use std::ops::RangeInclusive;
+
+use num::{BigInt, ToPrimitive};
+
+use super::first_win;
+
+/// How far the boat goes, for each hold of a race
+pub trait BoatModel {
+    /// How far the boat goes holding the button for `hold` ms (at most `time`)
+    /// of a race lasting `time` ms
+    fn distance(&self, hold: u64, time: u64) -> u128;
+
+    /// The holds which beat `record`, worked out directly, if we know how
+    fn closed_form(&self, _time: u64, _record: u128) -> Option<RangeInclusive<u64>> {
+        None
+    }
+
+    /// The holds which beat `record` (empty if none do)
+    fn winning_holds(&self, time: u64, record: u128) -> RangeInclusive<u64> {
+        self.closed_form(time, record)
+            .unwrap_or_else(|| bisect(self, time, record))
+    }
+}
+
+/// The speed goes up by 1 for every ms held, as in the puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Linear;
+
+/// The speed is the square of the time held.
+///
+/// Long races go further than a `u128` counts (from about 1.3e13 ms), so
+/// distances saturate at `u128::MAX`: still never falling before the peak, or
+/// rising after it, but a record of `u128::MAX` can't be beaten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quadratic;
+
+/// As [`Linear`], but the speed goes no higher than `top_speed`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capped {
+    pub top_speed: u64,
+}
+
+/// As [`Linear`], but once let go the boat slows by `drag` every ms until it stops
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Friction {
+    pub drag: u64,
+}
+
+impl BoatModel for Linear {
+    fn distance(&self, hold: u64, time: u64) -> u128 {
+        hold as u128 * (time - hold) as u128
+    }
+
+    fn closed_form(&self, time: u64, record: u128) -> Option<RangeInclusive<u64>> {
+        let first = first_win(&BigInt::from(time), &BigInt::from(record));
+        Some(match first.and_then(|t| t.to_u64()) {
+            Some(t) => t..=time - t,
+            None => no_holds(),
+        })
+    }
+}
+
+impl BoatModel for Quadratic {
+    fn distance(&self, hold: u64, time: u64) -> u128 {
+        (hold as u128).pow(2).saturating_mul((time - hold) as u128)
+    }
+}
+
+impl BoatModel for Capped {
+    fn distance(&self, hold: u64, time: u64) -> u128 {
+        hold.min(self.top_speed) as u128 * (time - hold) as u128
+    }
+
+    /// Up to top speed we go as [`Linear`] does. Past it, we go
+    /// top_speed * (time - hold), which beats the record while
+    /// hold < time - record / top_speed.
+    fn closed_form(&self, time: u64, record: u128) -> Option<RangeInclusive<u64>> {
+        let top = self.top_speed;
+        let linear = Linear.closed_form(time, record)?;
+        // holding for 0 goes nowhere, so a top speed of 0 never wins
+        if linear.is_empty() || *linear.start() > top {
+            return Some(no_holds());
+        }
+        let mut end = (*linear.end()).min(top);
+        let coasting = (time as u128).checked_sub(record / top as u128 + 1);
+        if let Some(last) = coasting.filter(|&t| t > top as u128) {
+            end = last as u64;
+        }
+        Some(*linear.start()..=end)
+    }
+}
+
+impl BoatModel for Friction {
+    fn distance(&self, hold: u64, time: u64) -> u128 {
+        let (speed, drag) = (hold as u128, self.drag as u128);
+        let moving = (time - hold) as u128;
+        // the boat has stopped after speed / drag + 1 ms
+        let ms = match drag {
+            0 => moving,
+            _ => moving.min(speed / drag + 1),
+        };
+        ms * speed - drag * ms * ms.saturating_sub(1) / 2
+    }
+}
+
+/// An empty run of holds
+fn no_holds() -> RangeInclusive<u64> {
+    RangeInclusive::new(1, 0)
+}
+
+/// The first of `lo..hi` where `pred` holds, given that it holds from there on
+/// (or `hi`, if it never does)
+fn partition_point(mut lo: u64, mut hi: u64, pred: impl Fn(u64) -> bool) -> u64 {
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match pred(mid) {
+            true => hi = mid,
+            false => lo = mid + 1,
+        }
+    }
+    lo
+}
+
+/// Find the peak, where holding any longer gets us no further, then where
+/// each side of it crosses the record
+fn bisect(model: &(impl BoatModel + ?Sized), time: u64, record: u128) -> RangeInclusive<u64> {
+    let d = |hold| model.distance(hold, time);
+
+    let peak = partition_point(0, time, |t| d(t + 1) <= d(t));
+    if d(peak) <= record {
+        return no_holds();
+    }
+    let first = partition_point(0, peak, |t| d(t) > record);
+    // the last hold that wins is the one before the next that doesn't, without
+    // looking past `time` (which may be u64::MAX)
+    let last = partition_point(peak, time, |t| d(t + 1) <= record);
+    first..=last
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The holds which beat `record`, trying every one
+    fn brute_force(model: &impl BoatModel, time: u64, record: u128) -> Vec<u64> {
+        (0..=time)
+            .filter(|&t| model.distance(t, time) > record)
+            .collect()
+    }
+
+    /// Check `model` every which way, for every race up to `max_time` ms
+    /// against every record up to its best
+    fn agrees(model: &impl BoatModel, max_time: u64) {
+        for time in 0..=max_time {
+            let best = (0..=time).map(|t| model.distance(t, time)).max().unwrap();
+            for record in 0..=best + 1 {
+                let expected = brute_force(model, time, record);
+                let bisected: Vec<u64> = bisect(model, time, record).collect();
+                assert_eq!(bisected, expected, "time {time}, record {record}");
+                if let Some(closed) = model.closed_form(time, record) {
+                    let closed: Vec<u64> = closed.collect();
+                    assert_eq!(closed, expected, "time {time}, record {record}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn linear() {
+        assert_eq!(Linear.winning_holds(7, 9), 2..=5);
+        assert_eq!(Linear.winning_holds(30, 200), 11..=19);
+        assert_eq!(Linear.winning_holds(71530, 940200).count(), 71503);
+        assert!(Linear.winning_holds(4, 4).is_empty());
+        agrees(&Linear, 30);
+    }
+
+    #[test]
+    fn quadratic() {
+        // 3 * 3 * 3 = 27, 4 * 4 * 2 = 32 and 5 * 5 * 1 = 25 all beat 24
+        assert_eq!(Quadratic.winning_holds(6, 24), 3..=5);
+        assert_eq!(Quadratic.winning_holds(6, 30), 4..=4);
+        agrees(&Quadratic, 25);
+    }
+
+    #[test]
+    fn capped() {
+        // the best we can do is 3 * 4 at 3 or 4 * 3 at 4 (then 4 * 2 at 5)
+        assert_eq!(Capped { top_speed: 4 }.winning_holds(7, 11), 3..=4);
+        for top_speed in 0..8 {
+            agrees(&Capped { top_speed }, 20);
+        }
+        // so fast that the cap never matters
+        let free = Capped {
+            top_speed: u64::MAX,
+        };
+        assert_eq!(free.winning_holds(30, 200), 11..=19);
+    }
+
+    #[test]
+    fn friction() {
+        // with drag 2, holding 6 of 10 goes 6 + 4 + 2 + 0, but holding 7 goes
+        // 7 + 5 + 3, the best there is (8 goes 8 + 6)
+        let model = Friction { drag: 2 };
+        assert_eq!(model.distance(6, 10), 12);
+        assert_eq!(model.distance(7, 10), 15);
+        assert_eq!(model.winning_holds(10, 14), 7..=7);
+        for drag in 0..5 {
+            agrees(&Friction { drag }, 20);
+        }
+        // no drag is no friction
+        assert_eq!(Friction { drag: 0 }.winning_holds(30, 200), 11..=19);
+    }
+
+    #[test]
+    fn long_races() {
+        // far too many holds to try one by one
+        let time = 4_000_000_000;
+        let record = 3_999_999_999_999_999_999;
+        assert_eq!(
+            bisect(&Linear, time, record),
+            Linear.winning_holds(time, record)
+        );
+        let holds = Quadratic.winning_holds(time, 10u128.pow(27));
+        assert!(!holds.is_empty());
+        let (a, b) = (*holds.start(), *holds.end());
+        assert!(Quadratic.distance(a - 1, time) <= 10u128.pow(27));
+        assert!(Quadratic.distance(a, time) > 10u128.pow(27));
+        assert!(Quadratic.distance(b, time) > 10u128.pow(27));
+        assert!(Quadratic.distance(b + 1, time) <= 10u128.pow(27));
+    }
+
+    #[test]
+    fn longest_race() {
+        // the best hold would go about 9e56, well past what a u128 holds
+        let time = u64::MAX;
+        let peak = time / 3 * 2;
+        assert_eq!(Quadratic.distance(peak, time), u128::MAX);
+
+        let record = 10u128.pow(38);
+        let holds = Quadratic.winning_holds(time, record);
+        let (a, b) = (*holds.start(), *holds.end());
+        assert!(a < peak && peak < b);
+        assert!(Quadratic.distance(a - 1, time) <= record);
+        assert!(Quadratic.distance(a, time) > record);
+        assert!(Quadratic.distance(b, time) > record);
+        assert!(Quadratic.distance(b + 1, time) <= record);
+
+        assert!(Quadratic.winning_holds(time, u128::MAX).is_empty());
+    }
+}
@@ -0,0 +1,242 @@
+use anyhow::Result;
+use aoc_common::{Answer, ParseError, Solution};
+use num::{BigInt, Signed, Zero};
+
+mod boat;
+
+pub use boat::{BoatModel, Capped, Friction, Linear, Quadratic};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = Sheet;
+
+    fn parse(infile: &str) -> Result<Sheet> {
+        Ok(parse_sheet(infile)?)
+    }
+    fn part_1(input: &Sheet) -> Result<Answer> {
+        Ok(part_1(input).into())
+    }
+    fn part_2(input: &Sheet) -> Result<Answer> {
+        Ok(part_2(input).into())
+    }
+}
+
+/// The sheet of paper: each column is a race, and all the columns run
+/// together (ignoring the spaces) are the one long race of part 2
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sheet {
+    races: Vec<Race>,
+    /// The time and distance of the one long race
+    long_race: (BigInt, BigInt),
+}
+
+fn part_1(sheet: &Sheet) -> usize {
+    sheet
+        .races
+        .iter()
+        .map(|r| {
+            strategise(r.time)
+                .iter()
+                .filter(|d| **d > r.distance)
+                .count()
+        })
+        .product()
+}
+fn part_2(sheet: &Sheet) -> BigInt {
+    let (time, dist) = &sheet.long_race;
+    winning_holds(time, dist)
+}
+
+/// How many whole ms of holding the button beat `distance` in a race lasting `time`
+pub fn winning_holds(time: &BigInt, distance: &BigInt) -> BigInt {
+    match first_win(time, distance) {
+        Some(t) => time - 2 * t + 1,
+        None => BigInt::zero(),
+    }
+}
+
+/// The shortest hold which beats `distance` in a race lasting `time`, if any
+/// does. By symmetry, the longest is `time` less that.
+///
+/// Holding for t goes (time - t) * t, so we win wherever
+/// t² - time·t + distance < 0, strictly between the roots
+/// (time ± √(time² - 4·distance)) / 2. The integer square root puts us at most
+/// a step from the lower root, so we step to the first t that wins.
+pub fn first_win(time: &BigInt, distance: &BigInt) -> Option<BigInt> {
+    let discriminant: BigInt = time * time - 4 * distance;
+    if discriminant.is_negative() {
+        return None;
+    }
+    let goes = |t: &BigInt| (time - t) * t;
+
+    let mut t: BigInt = (time - discriminant.sqrt()) / 2;
+    while t.is_positive() && goes(&(&t - 1)) > *distance {
+        t -= 1;
+    }
+    while 2 * &t <= *time && goes(&t) <= *distance {
+        t += 1;
+    }
+    (2 * &t <= *time).then_some(t)
+}
+
+fn parse_sheet(infile: &str) -> Result<Sheet, ParseError> {
+    let mut lines = infile.lines();
+    let end = &infile[infile.len()..];
+
+    // the line, and the numbers on it
+    let mut numbers = |label: &str| -> Result<(&str, Vec<&str>), ParseError> {
+        let line = lines
+            .next()
+            .ok_or_else(|| ParseError::new(infile, end, format!("a `{label}` line")))?;
+        let Some(rest) = line.strip_prefix(label) else {
+            let found = line.split_whitespace().next().unwrap_or(&line[..0]);
+            return Err(ParseError::new(infile, found, format!("`{label}`")));
+        };
+        let words: Vec<&str> = rest.split_whitespace().collect();
+        if let Some(bad) = words
+            .iter()
+            .find(|w| !w.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return Err(ParseError::new(infile, bad, "a number"));
+        }
+        Ok((line, words))
+    };
+    let (time_line, times) = numbers("Time:")?;
+    let (dist_line, dists) = numbers("Distance:")?;
+
+    if times.is_empty() {
+        return Err(ParseError::new(
+            infile,
+            &time_line[time_line.len()..],
+            "a time",
+        ));
+    }
+    match dists.get(times.len()) {
+        Some(extra) => return Err(ParseError::new(infile, extra, "the end of the line")),
+        None if dists.len() < times.len() => {
+            let missing = &dist_line[dist_line.len()..];
+            return Err(ParseError::new(
+                infile,
+                missing,
+                "a distance for every race",
+            ));
+        }
+        None => {}
+    }
+
+    let number = |s: &str| {
+        s.parse()
+            .map_err(|_| ParseError::new(infile, s, "a number of no more than 20 digits"))
+    };
+    let races = times
+        .iter()
+        .zip(&dists)
+        .map(|(t, d)| {
+            Ok(Race {
+                time: number(t)?,
+                distance: number(d)?,
+            })
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    let long = |words: Vec<&str>| words.concat().parse().expect("all digits");
+    Ok(Sheet {
+        races,
+        long_race: (long(times), long(dists)),
+    })
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+struct Race {
+    time: usize,
+    distance: usize,
+}
+
+/// For each whole ms you spend holding the button,
+/// the boat's speed increases by 1 ms/s
+/// but you only have `total_time` to hold and go
+/// returns a vec of distances where the index is the # of ms spent holding
+fn strategise(total_time: usize) -> Vec<usize> {
+    (0..=total_time).map(|t| (total_time - t) * t).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r"Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(&parse_sheet(EXAMPLE_1).unwrap()), 288);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(&parse_sheet(EXAMPLE_1).unwrap()), 71503.into());
+    }
+
+    #[test]
+    fn exactly_as_strategised() {
+        for time in 0..40 {
+            let best = strategise(time).into_iter().max().unwrap();
+            for distance in 0..=best + 1 {
+                let wins = strategise(time).iter().filter(|d| **d > distance).count();
+                assert_eq!(
+                    winning_holds(&time.into(), &distance.into()),
+                    wins.into(),
+                    "time {time}, distance {distance}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn huge_races() {
+        let big: BigInt = BigInt::from(10).pow(30);
+        // anything but not holding at all, or not letting go
+        assert_eq!(winning_holds(&big, &BigInt::zero()), &big - 1);
+
+        // only holding for exactly half the time, going (10^30)² = 10^60, will do
+        let time = 2 * &big;
+        let just_under = &big * &big - 1;
+        assert_eq!(winning_holds(&time, &just_under), BigInt::from(1));
+        assert_eq!(winning_holds(&time, &(just_under + 1)), BigInt::zero());
+
+        // the roots are 10^30 and 10^30 + 1 exactly: neither wins
+        let time = 2 * &big + 1;
+        let distance = &big * (&big + 1);
+        assert_eq!(winning_holds(&time, &distance), BigInt::zero());
+        assert_eq!(winning_holds(&time, &(distance - 1)), BigInt::from(2));
+    }
+
+    #[test]
+    fn unparseable() {
+        let e = parse_sheet("Time: 7 15\nDistance: 9 4x0").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 13, "4x0"));
+
+        let e = parse_sheet("Time: 7 15\nDistance: 9").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 12, ""));
+
+        let e = parse_sheet("Time: 7\nDistance: 9 40").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 13, "40"));
+
+        let e = parse_sheet("Time: 7").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 8));
+        assert!(parse_sheet("Tim: 7\nDistance: 9").is_err());
+
+        // fine for part 2, but too long for a race of part 1
+        assert!(parse_sheet("Time: 123456789012345678901\nDistance: 9").is_err());
+    }
+}
+
+/*
+
+
+
+
+
+*/
@@ -0,0 +1,232 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use aoc_common::{Answer, ParseError, Solution};
+use counter::Counter;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Input = Vec<Bid>;
+
+    fn parse(infile: &str) -> Result<Vec<Bid>> {
+        Ok(parse_bids(infile)?)
+    }
+    fn part_1(input: &Vec<Bid>) -> Result<Answer> {
+        Ok(part_1(input).into())
+    }
+    fn part_2(input: &Vec<Bid>) -> Result<Answer> {
+        Ok(part_2(input).into())
+    }
+}
+
+/// One line of the input: `32T3K 765`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bid {
+    hand: String,
+    bid: usize,
+}
+
+fn parse_bids(infile: &str) -> Result<Vec<Bid>, ParseError> {
+    infile
+        .lines()
+        .map(|line| {
+            let (hand, bid) = line.split_once(' ').ok_or_else(|| {
+                ParseError::new(infile, &line[line.len()..], "a space, then a bid")
+            })?;
+
+            if let Some((i, c)) = hand
+                .char_indices()
+                .find(|(_, c)| !PartOne::STRENGTH.contains(*c))
+            {
+                let found = &hand[i..i + c.len_utf8()];
+                return Err(ParseError::new(
+                    infile,
+                    found,
+                    "a card (2-9, T, J, Q, K or A)",
+                ));
+            }
+            if hand.len() != 5 {
+                return Err(ParseError::new(infile, hand, "a hand of 5 cards"));
+            }
+
+            let bid = bid.trim();
+            let bid = bid
+                .parse()
+                .map_err(|_| ParseError::new(infile, bid, "a bid"))?;
+            Ok(Bid {
+                hand: hand.to_string(),
+                bid,
+            })
+        })
+        .collect()
+}
+
+pub trait Hand {
+    fn score(&self) -> usize;
+}
+
+impl std::cmp::Ord for dyn Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.score().cmp(&other.score())
+    }
+}
+
+impl PartialOrd for dyn Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Eq for dyn Hand {}
+
+impl PartialEq for dyn Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+pub enum HandType {
+    HighCard = 1,
+    OnePair = 2,
+    TwoPair = 3,
+    ThreeOfAKind = 4,
+    FullHouse = 5,
+    FourOfAKind = 6,
+    FiveOfAKind = 7,
+}
+
+impl HandType {
+    fn from_cards(cards: &str) -> HandType {
+        let tops = cards.chars().collect::<Counter<_>>().most_common_ordered();
+        match tops[0].1 {
+            2 => match tops[1].1 {
+                2 => Self::TwoPair,
+                _ => Self::OnePair,
+            },
+            3 => match tops[1].1 {
+                2 => Self::FullHouse,
+                _ => Self::ThreeOfAKind,
+            },
+            4 => Self::FourOfAKind,
+            5 => Self::FiveOfAKind,
+            _ => Self::HighCard,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+pub struct PartOne(String);
+
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+pub struct PartTwo(String);
+
+impl PartOne {
+    const STRENGTH: &str = "23456789TJQKA";
+}
+
+impl PartTwo {
+    const STRENGTH: &str = "J23456789TQKA";
+}
+
+impl Hand for PartOne {
+    fn score(&self) -> usize {
+        let mut out = HandType::from_cards(&self.0) as usize;
+        for c in self.0.chars() {
+            out *= 100;
+            out += Self::STRENGTH.find(c).expect("Illegal card");
+        }
+        out
+    }
+}
+impl Hand for PartTwo {
+    fn score(&self) -> usize {
+        let avail: HashSet<String> = self.0.chars().map(|c| c.to_string()).collect();
+
+        let mut out = avail
+            .iter()
+            .map(|c| self.0.replace('J', c))
+            .map(|s| HandType::from_cards(&s) as usize)
+            .max()
+            .unwrap_or_default();
+        for c in self.0.chars() {
+            out *= 100;
+            out += Self::STRENGTH.find(c).expect("Illegal card");
+        }
+        out
+    }
+}
+
+/// As is tradition, the sample passes but the first answer didn't
+fn part_1(bids: &[Bid]) -> usize {
+    let mut input: Vec<(usize, PartOne, usize)> = bids
+        .iter()
+        .map(|b| (PartOne(b.hand.clone()), b.bid))
+        .map(|(h, b)| (h.score(), h, b))
+        .collect();
+
+    input.sort_unstable();
+
+    input
+        .iter()
+        .enumerate()
+        // .map(|(i, x)| {
+        //     println!("{}\t{}\t{}\t{}", i + 1, x.0, x.1 .0, x.2);
+        //     (i, x)
+        // })
+        .map(|(i, (_, _, b))| (i + 1) * b)
+        .sum()
+}
+fn part_2(bids: &[Bid]) -> usize {
+    let mut input: Vec<(usize, PartTwo, usize)> = bids
+        .iter()
+        .map(|b| (PartTwo(b.hand.clone()), b.bid))
+        .map(|(h, b)| (h.score(), h, b))
+        .collect();
+
+    input.sort_unstable();
+
+    input
+        .iter()
+        .enumerate()
+        // .map(|(i, x)| {
+        //     println!("{}\t{}\t{}\t{}", i + 1, x.0, x.1 .0, x.2);
+        //     (i, x)
+        // })
+        .map(|(i, (_, _, b))| (i + 1) * b)
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r"32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(&parse_bids(EXAMPLE_1).unwrap()), 6440);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(&parse_bids(EXAMPLE_1).unwrap()), 5905);
+    }
+
+    #[test]
+    fn bad_bids() {
+        let e = parse_bids("32T3K 765\nT55X5 684").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 4, "X"));
+
+        let e = parse_bids("32T3K 7x5").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (1, 7, "7x5"));
+
+        let e = parse_bids("KK6 28").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (1, 1, "KK6"));
+        assert!(parse_bids("32T3K").is_err());
+    }
+}
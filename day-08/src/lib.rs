@@ -0,0 +1,217 @@
+use anyhow::{Context, Result};
+use aoc_common::{Answer, ParseError, Solution};
+use num_integer::Integer;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    /// The directions, and the network
+    type Input = (Vec<Dir>, Graph);
+
+    fn parse(infile: &str) -> Result<(Vec<Dir>, Graph)> {
+        Ok(parse_input(infile)?)
+    }
+    fn part_1(input: &(Vec<Dir>, Graph)) -> Result<Answer> {
+        Ok(part_1(&input.0, &input.1)?.into())
+    }
+    fn part_2(input: &(Vec<Dir>, Graph)) -> Result<Answer> {
+        Ok(part_2(&input.0, &input.1)?.into())
+    }
+}
+
+/// Which way to go at a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dir {
+    L,
+    R,
+}
+
+type NodeName = String;
+
+pub type Graph = std::collections::HashMap<NodeName, (NodeName, NodeName)>;
+
+/// The directions and the network, which must have an `AAA` to start at and a
+/// `ZZZ` to get to
+fn parse_input(infile: &str) -> Result<(Vec<Dir>, Graph), ParseError> {
+    let (dirs, graph) = parse_network(infile)?;
+    for name in ["AAA", "ZZZ"] {
+        if !graph.contains_key(name) {
+            let end = &infile[infile.len()..];
+            return Err(ParseError::new(infile, end, format!("a node {name}")));
+        }
+    }
+    Ok((dirs, graph))
+}
+
+/// The directions and the network, where every node leads to nodes on the map
+fn parse_network(infile: &str) -> Result<(Vec<Dir>, Graph), ParseError> {
+    let mut lines = infile.lines();
+    let first = lines.next().unwrap_or_default();
+    let dirs = first
+        .char_indices()
+        .map(|(i, c)| match c {
+            'L' => Ok(Dir::L),
+            'R' => Ok(Dir::R),
+            _ => {
+                let found = &first[i..i + c.len_utf8()];
+                Err(ParseError::new(infile, found, "a direction (L or R)"))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if dirs.is_empty() {
+        return Err(ParseError::new(infile, first, "a direction (L or R)"));
+    }
+
+    let mut graph = Graph::new();
+    let mut leads_to = vec![];
+    for line in lines {
+        if line.is_empty() {
+            continue;
+        }
+        let node = || ParseError::new(infile, line, "a node, like `AAA = (BBB, CCC)`");
+        let (name, rhs) = line.split_once(" = ").ok_or_else(node)?;
+        let (left, right) = rhs
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .and_then(|s| s.split_once(", "))
+            .ok_or_else(node)?;
+        graph.insert(name.to_string(), (left.to_string(), right.to_string()));
+        leads_to.extend([left, right]);
+    }
+
+    if let Some(dangling) = leads_to.into_iter().find(|n| !graph.contains_key(*n)) {
+        return Err(ParseError::new(infile, dangling, "a node on the map"));
+    }
+    Ok((dirs, graph))
+}
+
+/// Where going `dir` from `node` leads
+fn step<'a>(graph: &'a Graph, node: &str, dir: Dir) -> Result<&'a NodeName> {
+    let (left, right) = graph
+        .get(node)
+        .with_context(|| format!("no node {node} on the map"))?;
+    Ok(match dir {
+        Dir::L => left,
+        Dir::R => right,
+    })
+}
+
+/// Basically a finite state machine with a step counter
+fn part_1(dirs: &[Dir], graph: &Graph) -> Result<usize> {
+    // We don't quite need a search algo
+
+    let mut step_count = 0;
+    let mut cur = "AAA";
+    for &dir in dirs.iter().cycle() {
+        if cur == "ZZZ" {
+            break;
+        }
+        cur = step(graph, cur, dir)?;
+        step_count += 1;
+    }
+    Ok(step_count)
+}
+
+/// Basically a finite state machine with a step counter
+fn part_2(dirs: &[Dir], graph: &Graph) -> Result<usize> {
+    let end_a_nodes: Vec<&NodeName> = graph.keys().filter(|s| s.ends_with('A')).collect();
+
+    // We don't quite need a search algo
+    // It might be heat death of the universe to simulate this directly
+    // Let's simulate each one separately and take the LCM
+
+    let mut step_counts: Vec<usize> = vec![];
+
+    for n in end_a_nodes {
+        let mut step_count = 0;
+        let mut cur = n;
+        for &dir in dirs.iter().cycle() {
+            if cur.ends_with('Z') {
+                break;
+            }
+            cur = step(graph, cur, dir)?;
+            step_count += 1;
+        }
+        step_counts.push(step_count)
+    }
+
+    step_counts
+        .into_iter()
+        .reduce(|acc, e| acc.lcm(&e))
+        .context("no node ending in A to start from")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r"RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    const EXAMPLE_2: &str = r"LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
+    const EXAMPLE_3: &str = r"LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn part_1_example_1() {
+        let (dirs, graph) = parse_input(EXAMPLE_1).unwrap();
+        assert_eq!(part_1(&dirs, &graph).unwrap(), 2);
+    }
+    #[test]
+    fn part_1_example_2() {
+        let (dirs, graph) = parse_input(EXAMPLE_2).unwrap();
+        assert_eq!(part_1(&dirs, &graph).unwrap(), 6);
+    }
+
+    #[test]
+    fn part_2_example() {
+        // no AAA or ZZZ here, so only part 2 makes sense of it
+        assert!(parse_input(EXAMPLE_3).is_err());
+        let (dirs, graph) = parse_network(EXAMPLE_3).unwrap();
+        assert_eq!(part_2(&dirs, &graph).unwrap(), 6);
+    }
+
+    #[test]
+    fn bad_maps() {
+        let e = parse_input("LXR\n\nAAA = (AAA, AAA)").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (1, 2, "X"));
+
+        let e = parse_input("\n\nAAA = (AAA, AAA)").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 1));
+
+        // BBB leads nowhere
+        let e = parse_input("L\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (3, 8, "BBB"));
+
+        let e = parse_input("L\n\nAAA = (AAA, AAA)").unwrap_err();
+        assert_eq!(e.found, "");
+        assert!(e.to_string().contains("ZZZ"));
+    }
+
+    #[test]
+    fn nowhere_to_start() {
+        let (dirs, graph) = parse_network("L\n\nBBB = (BBB, BBB)").unwrap();
+        assert!(part_2(&dirs, &graph).is_err());
+    }
+}
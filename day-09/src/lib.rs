@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use aoc_common::{Answer, ParseError, Solution};
+use itertools::Itertools;
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Input = Vec<Vec<isize>>;
+
+    fn parse(infile: &str) -> Result<Vec<Vec<isize>>> {
+        Ok(parse_histories(infile)?)
+    }
+    fn part_1(input: &Vec<Vec<isize>>) -> Result<Answer> {
+        Ok(part_1(input).into())
+    }
+    fn part_2(input: &Vec<Vec<isize>>) -> Result<Answer> {
+        Ok(part_2(input).into())
+    }
+}
+
+/// Each line's history of readings
+fn parse_histories(infile: &str) -> Result<Vec<Vec<isize>>, ParseError> {
+    infile
+        .lines()
+        .filter(|s| !s.is_empty())
+        .map(|line| {
+            line.split_whitespace()
+                .map(|s| {
+                    s.parse()
+                        .map_err(|_| ParseError::new(infile, s, "a number"))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Sequence of differences
+fn part_1(histories: &[Vec<isize>]) -> isize {
+    histories
+        .iter()
+        .map(|history| {
+            let mut diffs: HashMap<usize, Vec<isize>> = HashMap::new();
+
+            diffs.insert(0, history.clone());
+
+            let mut curr_level = 0_usize;
+
+            while diffs
+                .get(&curr_level)
+                .is_some_and(|sx| sx.iter().any(|&x| x != 0))
+            {
+                // Take successive differences
+                let next_level = diffs
+                    .get(&curr_level)
+                    .unwrap()
+                    .iter()
+                    .tuple_windows()
+                    .map(|(p, n)| n - p)
+                    .collect();
+                curr_level += 1;
+                diffs.insert(curr_level, next_level);
+            }
+
+            let mut nv = 0;
+            for c in (0..curr_level).rev() {
+                let cv = nv;
+                nv = cv + *diffs.get(&c).unwrap().last().unwrap_or(&0_isize);
+            }
+            nv
+        })
+        .sum()
+}
+
+/// Sequence of differences
+fn part_2(histories: &[Vec<isize>]) -> isize {
+    histories
+        .iter()
+        .map(|history| {
+            let mut diffs: HashMap<usize, Vec<isize>> = HashMap::new();
+
+            diffs.insert(0, history.clone());
+
+            let mut curr_level = 0_usize;
+
+            while diffs
+                .get(&curr_level)
+                .is_some_and(|sx| sx.iter().any(|&x| x != 0))
+            {
+                // Take successive differences
+                let next_level = diffs
+                    .get(&curr_level)
+                    .unwrap()
+                    .iter()
+                    .tuple_windows()
+                    .map(|(p, n)| n - p)
+                    .collect();
+                curr_level += 1;
+                diffs.insert(curr_level, next_level);
+            }
+
+            let mut nv = 0;
+            for c in (0..curr_level).rev() {
+                let cv = nv;
+                nv = *diffs.get(&c).unwrap().first().unwrap_or(&0_isize) - cv;
+            }
+            nv
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r"0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(&parse_histories(EXAMPLE_1).unwrap()), 114);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(&parse_histories(EXAMPLE_1).unwrap()), 2);
+    }
+
+    #[test]
+    fn bad_reading() {
+        let e = parse_histories("0 3 6\n1 3 x 10").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 5, "x"));
+    }
+}
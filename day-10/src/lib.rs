@@ -0,0 +1,383 @@
+use anyhow::Result;
+use aoc_common::{
+    search::{flood, SearchState},
+    Answer, ParseError, Solution,
+};
+use mapgrid::{Bounds, Coord, DenseGrid, Direction, Direction::*, Grid as _};
+
+mod network;
+mod pipe_loop;
+
+pub use network::{classify, Network, Rule};
+pub use pipe_loop::{Orientation, PipeLoop, Region};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Map;
+
+    fn parse(infile: &str) -> Result<Map> {
+        Ok(load_grid(infile)?)
+    }
+    fn part_1(input: &Map) -> Result<Answer> {
+        Ok(part_1(input.start, &input.pipes).into())
+    }
+    fn part_2(input: &Map) -> Result<Answer> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+pub type Point = Coord;
+
+pub type Grid = std::collections::HashMap<Point, Vec<Point>>;
+
+/// The sketch of the pipes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map {
+    /// Where the animal is (denoted by S)
+    pub start: Point,
+    /// The pipe under S, going by the pipes which lead into it
+    pub start_glyph: char,
+    /// Where each pipe leads (including the one under S)
+    pub pipes: Grid,
+    pub bounds: Bounds,
+}
+
+impl Map {
+    /// The pipe at `k`, if there is one
+    pub fn glyph(&self, k: Point) -> Option<char> {
+        let dirs: Vec<Direction> = self
+            .pipes
+            .get(&k)?
+            .iter()
+            .filter_map(|&n| Direction::ALL.into_iter().find(|d| k + d.to_delta() == n))
+            .collect();
+        match dirs[..] {
+            [a, b] => glyph(a, b),
+            _ => None,
+        }
+    }
+}
+
+/// Which ways a pipe leads
+fn connections(tile: char) -> Option<(Direction, Direction)> {
+    match tile {
+        '|' => Some((North, South)),
+        '-' => Some((West, East)),
+        'L' => Some((North, East)),
+        'J' => Some((North, West)),
+        '7' => Some((West, South)),
+        'F' => Some((East, South)),
+        _ => None,
+    }
+}
+
+/// The pipe which leads both `a` and `b`
+fn glyph(a: Direction, b: Direction) -> Option<char> {
+    "|-LJ7F"
+        .chars()
+        .find(|&c| connections(c).is_some_and(|(x, y)| (x, y) == (a, b) || (y, x) == (a, b)))
+}
+
+/// Returns starting point (denoted by S)
+/// along with adjecency matrix
+fn load_grid(infile: &str) -> Result<Map, ParseError> {
+    let (tiles, mut grid) = read_pipes(infile)?;
+
+    let starting = tiles.iter().find(|(_, &tile)| tile == 'S').map(|(k, _)| k);
+
+    let Some(starting) = starting else {
+        return Err(ParseError::new(
+            infile,
+            &infile[infile.len()..],
+            "a starting tile S",
+        ));
+    };
+
+    let Some(start_glyph) = hidden_pipe(&grid, starting) else {
+        let offset = infile.find('S').unwrap_or_default();
+        return Err(ParseError::at(
+            infile,
+            offset,
+            1,
+            "a start joined to exactly two pipes",
+        ));
+    };
+    let (a, b) = connections(start_glyph).expect("a pipe");
+    grid.insert(
+        starting,
+        vec![starting + a.to_delta(), starting + b.to_delta()],
+    );
+
+    Ok(Map {
+        start: starting,
+        start_glyph,
+        pipes: grid,
+        bounds: tiles.bounds(),
+    })
+}
+
+/// Every tile, and where each pipe leads (not counting any under an S)
+fn read_pipes(infile: &str) -> Result<(DenseGrid<char>, Grid), ParseError> {
+    let tiles = DenseGrid::parse_with(
+        infile,
+        |c| "|-LJ7F.S".contains(c).then_some(c),
+        "a pipe (|, -, L, J, 7 or F), . or S",
+    )?;
+
+    let mut grid = Grid::new();
+    for (me, &tile) in tiles.iter() {
+        if let Some((a, b)) = connections(tile) {
+            grid.insert(me, vec![me + a.to_delta(), me + b.to_delta()]);
+        }
+    }
+    Ok((tiles, grid))
+}
+
+/// The pipe under an S at `k`: whichever joins up the two which lead into it,
+/// if exactly two do
+fn hidden_pipe(grid: &Grid, k: Point) -> Option<char> {
+    let joined: Vec<Direction> = Direction::ALL
+        .into_iter()
+        .filter(|d| {
+            grid.get(&(k + d.to_delta()))
+                .is_some_and(|v| v.contains(&k))
+        })
+        .collect();
+    match joined[..] {
+        [a, b] => glyph(a, b),
+        _ => None,
+    }
+}
+
+/// Following the pipes from tile to tile
+struct Pipes<'a>(&'a Grid);
+
+impl SearchState<Pipes<'_>> for Point {
+    type Cost = usize;
+
+    fn successors(&self, pipes: &Pipes) -> impl IntoIterator<Item = Point> {
+        pipes.0.get(self).into_iter().flatten().copied()
+    }
+
+    fn cost(&self, _: &Point, _: &Pipes) -> usize {
+        1
+    }
+
+    fn is_goal(&self, _: &Pipes) -> bool {
+        false
+    }
+}
+
+/// Squeezing between the pipes (at 2x scale), without leaving the box
+struct Gaps<'a> {
+    pipes: &'a Grid,
+    bounds: Bounds,
+}
+
+impl SearchState<Gaps<'_>> for Point {
+    type Cost = usize;
+
+    fn successors(&self, gaps: &Gaps) -> impl IntoIterator<Item = Point> {
+        self.neighbours8(&gaps.bounds)
+            .filter(|n| !gaps.pipes.contains_key(n))
+    }
+
+    fn cost(&self, _: &Point, _: &Gaps) -> usize {
+        1
+    }
+
+    fn is_goal(&self, _: &Gaps) -> bool {
+        false
+    }
+}
+
+/// Run a BFS from the starting point
+fn scale_2x(grid: &Grid, starting: Point) -> (Point, Grid) {
+    let mut out = Grid::with_capacity(grid.len() * 4);
+
+    let mut queue = std::collections::VecDeque::new();
+    queue.push_back(starting);
+
+    while let Some(here) = queue.pop_front() {
+        let twobours: Vec<Point> = grid.get(&here).unwrap().iter().map(|n| *n * 2).collect();
+
+        for n in twobours {
+            if !out.contains_key(&n) {
+                queue.push_back(n / 2);
+            }
+            let tween = (here * 2 + n) / 2;
+
+            out.entry(here * 2).or_default().push(tween);
+            out.entry(n).or_default().push(tween);
+            out.insert(tween, vec![here, n]);
+        }
+    }
+
+    /*
+        for p in grid {
+            let newbours: Vec<Point> = p.1.iter().map(|[x, y]| [x * 2, y * 2]).collect();
+            let [xx, yy] = [p.0[0] * 2, p.0[1] * 2];
+            out.entry([xx, yy]).or_insert(newbours.clone());
+
+            for n in newbours
+                .iter()
+                .map(|n| add([xx, yy], *n))
+                .map(|[x, y]| [x / 2, y / 2])
+            {}
+        }
+    */
+    (starting * 2, out)
+}
+
+/// - load grid
+/// - identify starting position
+/// - swap starting tile for actual tile
+///   - note: confirmed no spurious neighbours
+/// - BFS
+/// - Get max distance
+fn part_1(starting: Point, grid: &Grid) -> usize {
+    // println!("Starting at {starting:?}");
+    // println!("First neighbours are {:?}", grid.get(&starting).unwrap());
+    // println!("Grid:\n{grid:?}");
+
+    let filled = flood(&Pipes(grid), [starting]);
+
+    // println!("{filled:?}");
+
+    *filled.values().max().unwrap_or(&0)
+}
+
+/// Identify tiles enclosed by the loop
+fn part_2(map: &Map) -> Result<usize> {
+    Ok(PipeLoop::walk(map)?.enclosed())
+}
+
+/// Identify tiles enclosed by the loop, by filling in everything outside it.
+/// Slower than [`part_2`], but kept as a cross-check.
+///
+/// We can have a zero-width squeeze between tiles
+/// just not crossing the loop
+/// the simplest solution here would be to move to 2x scale
+pub fn enclosed_by_flood(starting1: Point, loop1: &Grid) -> usize {
+    let (_, loop2) = scale_2x(loop1, starting1);
+
+    let xmax = loop2.keys().map(|v| v.row).max().unwrap_or(0);
+    let ymax = loop2.keys().map(|v| v.col).max().unwrap_or(0);
+    let xmin = loop2.keys().map(|v| v.row).min().unwrap_or(0);
+    let ymin = loop2.keys().map(|v| v.col).min().unwrap_or(0);
+
+    // for each point on the edge, we run a BFS for all neighbours,
+    // filtering out ones we've already visited and those we know are in the loop
+
+    let edge = (xmin..=xmax)
+        .flat_map(|x| (ymin..=ymax).map(move |y| Coord::new(x, y)))
+        .filter(|k| k.row == xmin || k.row == xmax || k.col == ymin || k.col == ymax)
+        .filter(|k| !loop2.contains_key(k));
+
+    let gaps = Gaps {
+        pipes: &loop2,
+        bounds: [xmin..=xmax, ymin..=ymax],
+    };
+    let outside = flood(&gaps, edge);
+
+    (xmin..=xmax)
+        .flat_map(|x| (ymin..=ymax).map(move |y| Coord::new(x, y)))
+        .filter(|k| !loop2.contains_key(k) && !outside.contains_key(k))
+        .filter(|k| (k.row % 2 == 0) && (k.col % 2 == 0))
+        .count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1B: &str = r"-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+    const EXAMPLE_1D: &str = r"7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ";
+
+    const EXAMPLE_2A: &str = r"...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+    const EXAMPLE_2B: &str = r"..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+
+    #[test]
+    fn unknown_tile() {
+        let e = load_grid(".S-7\n.|X|\n.L-J").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 3, "X"));
+    }
+
+    #[test]
+    fn part_1b() {
+        let map = load_grid(EXAMPLE_1B).unwrap();
+        assert_eq!(part_1(map.start, &map.pipes), 4);
+    }
+    #[test]
+    fn part_1d() {
+        let map = load_grid(EXAMPLE_1D).unwrap();
+        assert_eq!(part_1(map.start, &map.pipes), 8);
+    }
+
+    #[test]
+    fn part_2_example_a() {
+        let map = load_grid(EXAMPLE_2A).unwrap();
+        assert_eq!(part_2(&map).unwrap(), 4);
+    }
+
+    #[test]
+    fn part_2_example() {
+        let map = load_grid(EXAMPLE_2B).unwrap();
+        assert_eq!(part_2(&map).unwrap(), 4);
+    }
+
+    #[test]
+    fn flood_agrees() {
+        for infile in [EXAMPLE_1B, EXAMPLE_1D, EXAMPLE_2A, EXAMPLE_2B] {
+            let map = load_grid(infile).unwrap();
+            assert_eq!(
+                part_2(&map).unwrap(),
+                enclosed_by_flood(map.start, &map.pipes),
+                "{infile}"
+            );
+        }
+    }
+
+    #[test]
+    fn start_glyph() {
+        let glyphs =
+            [EXAMPLE_1B, EXAMPLE_1D, EXAMPLE_2A].map(|f| load_grid(f).unwrap().start_glyph);
+        assert_eq!(glyphs, ['F', 'F', 'F']);
+
+        let map = load_grid("-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF").unwrap();
+        assert_eq!(map.glyph(map.start), Some('F'));
+        assert_eq!(map.glyph(Coord::new(3, 3)), Some('J'));
+        assert_eq!(map.glyph(Coord::new(9, 9)), None);
+
+        let e = load_grid(".|.\n-S-\n.|.").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+    }
+}
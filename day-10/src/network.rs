@@ -0,0 +1,251 @@
+//! Every closed loop on the map, not just the one through S.
+//!
+//! Any number of S may be on the map (or none), each standing for whichever
+//! pipe joins up the two which lead into it. Only pipes which lead to pipes
+//! leading straight back count, so junk is pruned away until all that's left
+//! is loops.
+
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use anyhow::{bail, Error, Result};
+use aoc_common::ParseError;
+use mapgrid::{Bounds, Coord, DenseGrid, Direction, Grid as _};
+
+use super::{connections, hidden_pipe, read_pipes, Grid, PipeLoop, Point, Region};
+
+/// Which tiles count as inside, when loops are nested
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// Inside an odd number of loops
+    EvenOdd,
+    /// Loops going round a tile don't cancel out: one clockwise and one
+    /// anticlockwise do, two clockwise don't
+    NonZero,
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "even-odd" => Ok(Rule::EvenOdd),
+            "non-zero" | "winding" => Ok(Rule::NonZero),
+            _ => bail!("expected even-odd or non-zero, got {s:?}"),
+        }
+    }
+}
+
+/// The pipes which form closed loops
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    /// Where each pipe leads, both ways joined up
+    pub pipes: Grid,
+    pub bounds: Bounds,
+}
+
+impl Network {
+    pub fn parse(infile: &str) -> Result<Self, ParseError> {
+        let (tiles, mut pipes) = read_pipes(infile)?;
+
+        let starts: Vec<(Point, char)> = tiles
+            .iter()
+            .filter(|(_, &tile)| tile == 'S')
+            .filter_map(|(k, _)| Some((k, hidden_pipe(&pipes, k)?)))
+            .collect();
+        for (k, glyph) in starts {
+            let (a, b) = connections(glyph).expect("a pipe");
+            pipes.insert(k, vec![k + a.to_delta(), k + b.to_delta()]);
+        }
+
+        // a pipe whose neighbour doesn't lead back can't be part of a loop,
+        // and then neither can that neighbour, and so on
+        let leads_back = |pipes: &Grid, k: &Point| {
+            pipes[k]
+                .iter()
+                .all(|n| pipes.get(n).is_some_and(|v| v.contains(k)))
+        };
+        let mut suspects: Vec<Point> = pipes.keys().copied().collect();
+        while let Some(k) = suspects.pop() {
+            if pipes.contains_key(&k) && !leads_back(&pipes, &k) {
+                suspects.extend(pipes.remove(&k).unwrap());
+            }
+        }
+
+        Ok(Self {
+            pipes,
+            bounds: tiles.bounds(),
+        })
+    }
+
+    /// Every loop, in the order of their first tile by row then column, each
+    /// walked from there
+    pub fn loops(&self) -> Result<Vec<PipeLoop>> {
+        let mut starts: Vec<Point> = self.pipes.keys().copied().collect();
+        starts.sort_by_key(|k| (k.row, k.col));
+
+        let mut seen = HashSet::new();
+        let mut out = vec![];
+        for k in starts {
+            if seen.contains(&k) {
+                continue;
+            }
+            let pipe_loop = PipeLoop::walk_from(&self.pipes, k)?;
+            seen.extend(pipe_loop.path.iter().copied());
+            out.push(pipe_loop);
+        }
+        Ok(out)
+    }
+}
+
+/// Every tile of the map: on one of `loops`, or inside or outside them by `rule`.
+///
+/// As for [`PipeLoop::classify`], we go along each row crossing the loops
+/// where they lead north, and keep count of how many times they go round:
+/// once more for each one we cross going north, and once less going south.
+pub fn classify(loops: &[PipeLoop], bounds: &Bounds, rule: Rule) -> DenseGrid<Region> {
+    let north = Direction::North.to_delta();
+
+    // which way each loop crosses between a tile and the one north of it
+    let mut crossings: HashMap<Point, isize> = HashMap::new();
+    for pipe_loop in loops {
+        let next = pipe_loop.path.iter().cycle().skip(1);
+        for (&a, &b) in pipe_loop.path.iter().zip(next) {
+            if b == a + north {
+                *crossings.entry(a).or_default() += 1;
+            } else if a == b + north {
+                *crossings.entry(b).or_default() -= 1;
+            }
+        }
+    }
+    let on_loop: HashSet<Point> = loops.iter().flat_map(|l| l.path.iter().copied()).collect();
+
+    let [rows, cols] = bounds;
+    let mut regions = DenseGrid::new(cols.clone().count(), rows.clone().count(), Region::Outside);
+    for row in rows.clone() {
+        let mut winding = 0;
+        for col in cols.clone() {
+            let k = Coord::new(row, col);
+            let inside = match rule {
+                Rule::EvenOdd => winding % 2 != 0,
+                Rule::NonZero => winding != 0,
+            };
+            regions[k] = if on_loop.contains(&k) {
+                Region::Loop
+            } else if inside {
+                Region::Inside
+            } else {
+                Region::Outside
+            };
+            winding += crossings.get(&k).copied().unwrap_or_default();
+        }
+    }
+    regions
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Orientation;
+
+    /// One loop round the edge, with a square inside it going the same way
+    /// round, a little loop beside that, and a stray `-` in between
+    const NESTED: &str = "\
+F-------7
+|F-7.-..|
+||.|.F7.|
+|L-J.LJ.|
+L-------J";
+
+    #[test]
+    fn loops() {
+        let network = Network::parse(NESTED).unwrap();
+        assert!(!network.pipes.contains_key(&Coord::new(1, 5)));
+
+        let loops = network.loops().unwrap();
+        let stats: Vec<_> = loops
+            .iter()
+            .map(|l| (l.path.len(), l.farthest(), l.enclosed()))
+            .collect();
+        assert_eq!(stats, [(24, 12, 21), (8, 4, 1), (4, 2, 0)]);
+        assert!(loops
+            .iter()
+            .all(|l| l.orientation == Orientation::Clockwise));
+    }
+
+    #[test]
+    fn junk_and_starts() {
+        // S can stand for any pipe, as often as we like, and a pipe leading
+        // off a loop doesn't spoil it
+        let network = Network::parse("S-7.S7\n|.|-LJ\nL-J...").unwrap();
+        let loops = network.loops().unwrap();
+        assert_eq!(loops.len(), 2);
+        assert_eq!(loops[1].path[0], Coord::new(0, 4));
+        assert!(!network.pipes.contains_key(&Coord::new(1, 3)));
+
+        // a broken loop is nothing but junk
+        let network = Network::parse("F-7\n|.|\nL-.").unwrap();
+        assert!(network.pipes.is_empty());
+        assert_eq!(network.loops().unwrap(), []);
+    }
+
+    #[test]
+    fn nested() {
+        let network = Network::parse(NESTED).unwrap();
+        let loops = network.loops().unwrap();
+        let count = |rule| {
+            let regions = classify(&loops, &network.bounds, rule);
+            let inside = regions.iter().filter(|(_, v)| **v == Region::Inside);
+            (inside.count(), regions[Coord::new(2, 2)])
+        };
+        assert_eq!(count(Rule::EvenOdd), (8, Region::Outside));
+        assert_eq!(count(Rule::NonZero), (9, Region::Inside));
+
+        // with the square turned round, the two rules agree
+        let reversed: Vec<PipeLoop> = loops
+            .iter()
+            .enumerate()
+            .map(|(i, l)| {
+                let mut l = l.clone();
+                if i == 1 {
+                    l.path.reverse();
+                }
+                l
+            })
+            .collect();
+        let regions = classify(&reversed, &network.bounds, Rule::NonZero);
+        assert_eq!(regions[Coord::new(2, 2)], Region::Outside);
+    }
+
+    #[test]
+    fn one_loop_as_before() {
+        let map = crate::load_grid(
+            "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........",
+        )
+        .unwrap();
+        let pipe_loop = PipeLoop::walk(&map).unwrap();
+        for rule in [Rule::EvenOdd, Rule::NonZero] {
+            assert_eq!(
+                classify(std::slice::from_ref(&pipe_loop), &map.bounds, rule),
+                pipe_loop.classify(&map)
+            );
+        }
+    }
+
+    #[test]
+    fn rule_names() {
+        assert_eq!("even-odd".parse::<Rule>().unwrap(), Rule::EvenOdd);
+        assert_eq!("winding".parse::<Rule>().unwrap(), Rule::NonZero);
+        assert!("odd".parse::<Rule>().is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! The loop through S, walked in order: which way round it goes, what's
+//! inside it, and a picture of it all.
+
+use std::{collections::HashSet, fmt};
+
+use anyhow::{bail, Result};
+use mapgrid::{polygon, Coord, DenseGrid, Direction, Grid as _};
+
+use super::{Grid, Map, Point};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    Anticlockwise,
+}
+
+/// Where a tile stands with respect to the loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    Loop,
+    Inside,
+    Outside,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipeLoop {
+    /// Every tile of the loop in order, starting (but not finishing) at S
+    pub path: Vec<Point>,
+    /// Which way round `path` goes, as drawn
+    pub orientation: Orientation,
+}
+
+impl PipeLoop {
+    /// Follow the pipes from S, first along the pipe it leads into first, until we're back
+    pub fn walk(map: &Map) -> Result<Self> {
+        Self::walk_from(&map.pipes, map.start)
+    }
+
+    /// Follow the pipes from the one at `start`, as for [`PipeLoop::walk`]
+    pub fn walk_from(pipes: &Grid, start: Point) -> Result<Self> {
+        let Some(&first) = pipes.get(&start).and_then(|v| v.first()) else {
+            bail!("there's no pipe at {start:?}");
+        };
+        let mut path = vec![start];
+        let (mut prev, mut here) = (start, first);
+
+        while here != start {
+            // the pipe here must lead back where we came from, and on somewhere else
+            let next = match pipes.get(&here).map(Vec::as_slice) {
+                Some(&[a, b]) if a == prev => b,
+                Some(&[a, b]) if b == prev => a,
+                _ => bail!("the pipes from {start:?} don't join up into a loop (at {here:?})"),
+            };
+            path.push(here);
+            (prev, here) = (here, next);
+        }
+
+        let orientation = match polygon::double_area(&path) > 0 {
+            true => Orientation::Clockwise,
+            false => Orientation::Anticlockwise,
+        };
+        Ok(Self { path, orientation })
+    }
+
+    /// How many steps it is along the loop to the tile farthest from the start
+    pub fn farthest(&self) -> usize {
+        self.path.len() / 2
+    }
+
+    /// How many tiles the loop encloses. Every tile of the loop is a corner of
+    /// a polygon, and Pick's theorem tells us how many tiles are strictly inside that.
+    pub fn enclosed(&self) -> usize {
+        polygon::interior_points(&self.path)
+    }
+
+    /// Every tile of the map: on the loop, or inside or outside it.
+    ///
+    /// Going along each row, we cross the loop at every loop tile which leads
+    /// north (`|`, `L` or `J`). Where we go along it instead (say `L--7`), that
+    /// counts one crossing, or two for a U-turn (`L--J`), which is what we want.
+    pub fn classify(&self, map: &Map) -> DenseGrid<Region> {
+        let on_loop: HashSet<Point> = self.path.iter().copied().collect();
+        let [rows, cols] = &map.bounds;
+        let mut regions =
+            DenseGrid::new(cols.clone().count(), rows.clone().count(), Region::Outside);
+
+        for row in rows.clone() {
+            let mut inside = false;
+            for col in cols.clone() {
+                let k = Coord::new(row, col);
+                regions[k] = if on_loop.contains(&k) {
+                    if map.pipes[&k].contains(&(k + Direction::North.to_delta())) {
+                        inside = !inside;
+                    }
+                    Region::Loop
+                } else if inside {
+                    Region::Inside
+                } else {
+                    Region::Outside
+                };
+            }
+        }
+        regions
+    }
+
+    /// The map with the loop drawn in box-drawing characters, and every other
+    /// tile marked `I` (inside) or `O` (outside)
+    pub fn render(&self, map: &Map) -> String {
+        let regions = self.classify(map);
+        let mut picture = DenseGrid::new(regions.width(), regions.height(), ' ');
+        for (k, region) in regions.iter() {
+            picture[k] = match region {
+                Region::Loop => map.glyph(k).map_or('?', box_drawing),
+                Region::Inside => 'I',
+                Region::Outside => 'O',
+            };
+        }
+        picture.visualise()
+    }
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Orientation::Clockwise => write!(f, "clockwise"),
+            Orientation::Anticlockwise => write!(f, "anticlockwise"),
+        }
+    }
+}
+
+fn box_drawing(pipe: char) -> char {
+    match pipe {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::load_grid;
+
+    const SQUARE: &str = ".....
+.S-7.
+.|.|.
+.L-J.
+.....";
+
+    #[test]
+    fn walk() {
+        let map = load_grid(SQUARE).unwrap();
+        let pipe_loop = PipeLoop::walk(&map).unwrap();
+        assert_eq!(pipe_loop.path.len(), 8);
+        assert_eq!(pipe_loop.path[..2], [Coord::new(1, 1), Coord::new(1, 2)]);
+        assert_eq!(pipe_loop.orientation, Orientation::Clockwise);
+
+        // the same loop, but with S up the right-hand side, so we set off north
+        let map = load_grid(&SQUARE.replace("S-7", "F-7").replace(".|.|.", ".|.S.")).unwrap();
+        assert_eq!(map.start_glyph, '|');
+        let pipe_loop = PipeLoop::walk(&map).unwrap();
+        assert_eq!(pipe_loop.orientation, Orientation::Anticlockwise);
+
+        let map = load_grid("S-7\n|.|\nL-.").unwrap();
+        assert!(PipeLoop::walk(&map).is_err());
+    }
+
+    #[test]
+    fn classify() {
+        let map = load_grid(SQUARE).unwrap();
+        let pipe_loop = PipeLoop::walk(&map).unwrap();
+        assert_eq!(
+            pipe_loop.render(&map),
+            "OOOOO\nO┌─┐O\nO│I│O\nO└─┘O\nOOOOO\n"
+        );
+
+        // squeezing between pipes doesn't get you inside
+        let map = load_grid(
+            "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........",
+        )
+        .unwrap();
+        let pipe_loop = PipeLoop::walk(&map).unwrap();
+        let regions = pipe_loop.classify(&map);
+        let count = |r| regions.iter().filter(|(_, v)| **v == r).count();
+        assert_eq!(count(Region::Inside), 4);
+        assert_eq!(count(Region::Loop), pipe_loop.path.len());
+        assert_eq!(regions[Coord::new(6, 2)], Region::Inside);
+        assert_eq!(regions[Coord::new(3, 3)], Region::Outside);
+    }
+}
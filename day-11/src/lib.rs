@@ -0,0 +1,177 @@
+use std::{collections::HashSet, ops::RangeInclusive};
+
+use anyhow::Result;
+use aoc_common::{Answer, ParseError, Solution};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = HashSet<(usize, usize)>;
+
+    fn parse(infile: &str) -> Result<HashSet<(usize, usize)>> {
+        Ok(load_universe(infile)?)
+    }
+    fn part_1(input: &HashSet<(usize, usize)>) -> Result<Answer> {
+        Ok(part_1(input).into())
+    }
+    fn part_2(input: &HashSet<(usize, usize)>) -> Result<Answer> {
+        Ok(part_2(input).into())
+    }
+}
+
+fn part_1(galaxies: &HashSet<(usize, usize)>) -> usize {
+    let expando = expand_universe(galaxies, 2);
+
+    // now for each pair of galaxies, we need the shortest path, and thence the sum thereof
+    path_pairs(expando)
+}
+fn part_2(galaxies: &HashSet<(usize, usize)>) -> usize {
+    let expando = expand_universe(galaxies, 1_000_000);
+
+    path_pairs(expando)
+}
+
+/// for each pair of galaxies, we need the shortest path, and thence the sum thereof
+fn path_pairs(input: HashSet<(usize, usize)>) -> usize {
+    let mut listy: Vec<(usize, usize)> = input.into_iter().collect();
+    listy.sort();
+
+    let mut total = 0_usize;
+
+    for (j, a) in listy.iter().enumerate() {
+        for b in listy.iter().skip(j) {
+            let x = a.0.abs_diff(b.0);
+            let y = a.1.abs_diff(b.1);
+
+            total += x + y;
+        }
+    }
+    total
+}
+
+fn bounds(input: &HashSet<(usize, usize)>) -> (RangeInclusive<usize>, RangeInclusive<usize>) {
+    let xmax = input.iter().map(|v| v.0).max().unwrap_or(0);
+    let ymax = input.iter().map(|v| v.1).max().unwrap_or(0);
+    let xmin = input.iter().map(|v| v.0).min().unwrap_or(0);
+    let ymin = input.iter().map(|v| v.1).min().unwrap_or(0);
+
+    (xmin..=xmax, ymin..=ymax)
+}
+
+fn load_universe(infile: &str) -> Result<HashSet<(usize, usize)>, ParseError> {
+    //! Galaxy: `#`
+    let mut galaxies = HashSet::new();
+    for (y, row) in infile.lines().enumerate() {
+        for (x, (i, c)) in row.char_indices().enumerate() {
+            match c {
+                '#' => {
+                    galaxies.insert((x, y));
+                }
+                '.' => {}
+                _ => {
+                    let found = &row[i..i + c.len_utf8()];
+                    return Err(ParseError::new(infile, found, "a galaxy (#) or space (.)"));
+                }
+            }
+        }
+    }
+    Ok(galaxies)
+}
+
+fn expand_universe(
+    galaxy_orig: &HashSet<(usize, usize)>,
+    factor: usize,
+) -> HashSet<(usize, usize)> {
+    let (xs, ys) = bounds(galaxy_orig);
+    let (xmin, xmax) = (*xs.start(), *xs.end());
+    let (ymin, ymax) = (*ys.start(), *ys.end());
+
+    let no_galaxy_col: Vec<usize> = (xmin..=xmax)
+        .filter(|x| !(ymin..=ymax).any(|y| galaxy_orig.contains(&(*x, y))))
+        .collect();
+    let no_galaxy_row: Vec<usize> = (ymin..=ymax)
+        .filter(|y| !(xmin..=xmax).any(|x| galaxy_orig.contains(&(x, *y))))
+        .collect();
+
+    // println!("Rows without galaxies:\t{no_galaxy_row:?}");
+    // println!("Cols without galaxies:\t{no_galaxy_col:?}");
+
+    // if a galaxy's position in a coordinate is strictly greater than the coordinates of N rows/columns then its new position is +N
+
+    let mut out = HashSet::new();
+    for &(x, y) in galaxy_orig {
+        let xplus = &no_galaxy_col.iter().filter(|t| **t < x).count();
+        let yplus = &no_galaxy_row.iter().filter(|t| **t < y).count();
+        // println!("Expanding ({x}, {y}) by ({xplus}, {yplus})");
+        out.insert((x + xplus * (factor - 1), y + yplus * (factor - 1)));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r"...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    const EXPANDO_1: &str = r"....#........
+.........#...
+#............
+.............
+.............
+........#....
+.#...........
+............#
+.............
+.............
+.........#...
+#....#.......";
+
+    #[test]
+    fn test_part_1_expando() {
+        let mut a = expand_universe(&load_universe(EXAMPLE_1).unwrap(), 2)
+            .into_iter()
+            .collect::<Vec<(usize, usize)>>();
+        a.sort();
+        let mut b = load_universe(EXPANDO_1)
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<(usize, usize)>>();
+        b.sort();
+        println!("{a:?}");
+        println!("{b:?}");
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn bad_tile() {
+        let e = load_universe("..#\n.X.").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 2, "X"));
+    }
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(&load_universe(EXAMPLE_1).unwrap()), 374);
+    }
+
+    #[test]
+    fn part_2_examples() {
+        let galaxies = load_universe(EXAMPLE_1).unwrap();
+        let x10 = expand_universe(&galaxies, 10);
+        let x100 = expand_universe(&galaxies, 100);
+        assert_eq!(path_pairs(x10), 1030);
+        assert_eq!(path_pairs(x100), 8410);
+    }
+}
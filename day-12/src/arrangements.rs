@@ -0,0 +1,231 @@
+//! Every arrangement of a row, one at a time, or one at random.
+//!
+//! Both walk the same choices as [`WaysTable`]: where to put the next group of
+//! damaged springs. The table tells us how many arrangements lie behind each
+//! choice, so we never go down a dead end, and can weight a random pick.
+
+use num::{bigint::RandBigInt, BigUint, Zero};
+use rand::Rng;
+
+use super::{Row, WaysTable};
+
+/// The arrangements of one row, and how many ways there are to finish it
+/// from each (position in the springs, groups placed so far).
+///
+/// Counted in a [`BigUint`], as for [`count_unfolded`](super::count_unfolded):
+/// a long enough row has more arrangements than any machine integer holds.
+pub struct Arrangements<'a> {
+    row: &'a Row,
+    table: WaysTable<BigUint>,
+}
+
+impl<'a> Arrangements<'a> {
+    pub fn new(row: &'a Row) -> Self {
+        Self {
+            row,
+            table: WaysTable::new(&row.springs, &row.counts),
+        }
+    }
+
+    /// How many arrangements there are in all
+    pub fn count(&self) -> BigUint {
+        self.ways(0, 0).clone()
+    }
+
+    fn ways(&self, line_idx: usize, counts_idx: usize) -> &BigUint {
+        self.table.get(line_idx, counts_idx)
+    }
+
+    /// Where the next group can start, so that the row can still be finished,
+    /// with where the rest of the row picks up and how many ways there are from there
+    fn next_group(&self, line_idx: usize, counts_idx: usize) -> Vec<(usize, usize, &BigUint)> {
+        let springs = self.row.springs.as_bytes();
+        let size = self.row.counts[counts_idx];
+
+        let mut out = vec![];
+        for start in line_idx..springs.len() {
+            let end = start + size;
+            if end > springs.len() {
+                break;
+            }
+            if !springs[start..end].contains(&b'.') && springs.get(end) != Some(&b'#') {
+                // step over the gap after the group, if there's room for one
+                let next = springs.len().min(end + 1);
+                let ways = self.ways(next, counts_idx + 1);
+                if !ways.is_zero() {
+                    out.push((start, next, ways));
+                }
+            }
+            if springs[start] == b'#' {
+                // can't leave a damaged spring out of the group
+                break;
+            }
+        }
+        out
+    }
+
+    /// Springs `line_idx..` of the arrangement where the next group starts at
+    /// `start` and the rest picks up at `next`
+    fn place(&self, line_idx: usize, counts_idx: usize, start: usize, next: usize) -> String {
+        let size = self.row.counts[counts_idx];
+        let mut s = ".".repeat(start - line_idx);
+        s.push_str(&"#".repeat(size));
+        s.push_str(&".".repeat(next - start - size));
+        s
+    }
+
+    /// Every arrangement as a string of `#` and `.`, lazily, leftmost groups first
+    pub fn iter(&self) -> Iter<'_, 'a> {
+        let stack = match self.ways(0, 0).is_zero() {
+            true => vec![],
+            false => vec![(0, 0, String::new())],
+        };
+        Iter { arr: self, stack }
+    }
+
+    /// An arrangement picked uniformly at random, if there are any
+    pub fn sample(&self, rng: &mut impl Rng) -> Option<String> {
+        if self.ways(0, 0).is_zero() {
+            return None;
+        }
+        let (mut line_idx, mut counts_idx) = (0, 0);
+        let mut out = String::new();
+
+        while counts_idx < self.row.counts.len() {
+            let choices = self.next_group(line_idx, counts_idx);
+            let total: BigUint = choices.iter().map(|c| c.2).sum();
+            let mut pick = rng.gen_biguint_below(&total);
+            let &(start, next, _) = choices
+                .iter()
+                .find(|c| {
+                    if pick < *c.2 {
+                        return true;
+                    }
+                    pick -= c.2;
+                    false
+                })
+                .expect("pick is less than the total");
+
+            out.push_str(&self.place(line_idx, counts_idx, start, next));
+            (line_idx, counts_idx) = (next, counts_idx + 1);
+        }
+        out.push_str(&".".repeat(self.row.springs.len() - line_idx));
+        Some(out)
+    }
+}
+
+/// See [`Arrangements::iter`]
+pub struct Iter<'m, 'a> {
+    arr: &'m Arrangements<'a>,
+    /// Partial arrangements still to finish: (position, groups placed, springs so far)
+    stack: Vec<(usize, usize, String)>,
+}
+
+impl Iterator for Iter<'_, '_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let counts = self.arr.row.counts.len();
+        let springs = self.arr.row.springs.len();
+
+        while let Some((line_idx, counts_idx, prefix)) = self.stack.pop() {
+            if counts_idx == counts {
+                return Some(prefix + &".".repeat(springs - line_idx));
+            }
+            let choices = self.arr.next_group(line_idx, counts_idx);
+            for (start, next, _) in choices.into_iter().rev() {
+                let s = prefix.clone() + &self.arr.place(line_idx, counts_idx, start, next);
+                self.stack.push((next, counts_idx + 1, s));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{count_unfolded, parse_rows, unfold};
+
+    /// `arrangement` has no unknowns, agrees with `row` on the known springs,
+    /// and its groups are the right sizes
+    fn fits(row: &Row, arrangement: &str) -> bool {
+        let agrees = row.springs.len() == arrangement.len()
+            && row
+                .springs
+                .chars()
+                .zip(arrangement.chars())
+                .all(|(r, a)| a != '?' && (r == '?' || r == a));
+        let groups: Vec<usize> = arrangement
+            .split('.')
+            .filter(|g| !g.is_empty())
+            .map(str::len)
+            .collect();
+        agrees && groups == row.counts
+    }
+
+    #[test]
+    fn enumerate() {
+        let row = parse_rows("?###???????? 3,2,1").unwrap().remove(0);
+        let arr = Arrangements::new(&row);
+        let all: Vec<String> = arr.iter().collect();
+
+        assert_eq!(all.len(), 10);
+        assert_eq!(all[0], ".###.##.#...");
+        assert_eq!(all[9], ".###....##.#");
+        assert!(all.iter().all(|a| fits(&row, a)));
+        assert!(
+            all.windows(2).all(|w| w[0] < w[1]),
+            "in order, and distinct"
+        );
+
+        let row = parse_rows("#.? 2").unwrap().remove(0);
+        assert_eq!(Arrangements::new(&row).iter().next(), None);
+    }
+
+    #[test]
+    fn lazily() {
+        let row = parse_rows("??????????????????????????????????????? 1,1,1,1,1").unwrap();
+        let arr = Arrangements::new(&row[0]);
+        assert_eq!(arr.count(), BigUint::from(324632u32));
+        let first: Vec<String> = arr.iter().take(2).collect();
+        assert!(first[1].starts_with("#.#.#.#.."));
+    }
+
+    #[test]
+    fn sample_uniformly() {
+        let mut rng = StdRng::seed_from_u64(12);
+        let row = parse_rows("?###???????? 3,2,1").unwrap().remove(0);
+        let arr = Arrangements::new(&row);
+
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for _ in 0..5000 {
+            let a = arr.sample(&mut rng).unwrap();
+            assert!(fits(&row, &a), "{a}");
+            *seen.entry(a).or_default() += 1;
+        }
+        assert_eq!(seen.len(), 10);
+        assert!(seen.values().all(|&n| (350..650).contains(&n)), "{seen:?}");
+
+        let row = parse_rows("#.? 2").unwrap().remove(0);
+        assert_eq!(Arrangements::new(&row).sample(&mut rng), None);
+    }
+
+    #[test]
+    fn unfolded_far() {
+        // far more arrangements than a u128 holds
+        let row = parse_rows("?????????? 1,1").unwrap().remove(0);
+        let rows = [unfold(&row, 20, "?")];
+        let arr = Arrangements::new(&rows[0]);
+        assert_eq!(arr.count(), count_unfolded(&[row], 20, "?"));
+        assert!(arr.count() > BigUint::from(u128::MAX));
+
+        assert!(arr.iter().next().unwrap().starts_with("#.#.#."));
+        let mut rng = StdRng::seed_from_u64(12);
+        assert!(fits(&rows[0], &arr.sample(&mut rng).unwrap()));
+    }
+}
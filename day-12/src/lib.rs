@@ -0,0 +1,490 @@
+use anyhow::Result;
+use aoc_common::{Answer, ParseError, Solution};
+use itertools::Itertools;
+use num::{rational::Ratio, BigUint, One, Zero};
+use rayon::prelude::*;
+use std::{fmt, ops::AddAssign};
+
+mod arrangements;
+mod nonogram;
+
+pub use arrangements::{Arrangements, Iter};
+pub use nonogram::{Nonogram, Picture};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Vec<Row>;
+
+    fn parse(infile: &str) -> Result<Vec<Row>> {
+        Ok(parse_rows(infile)?)
+    }
+    fn part_1(input: &Vec<Row>) -> Result<Answer> {
+        Ok(part_1(input).into())
+    }
+    fn part_2(input: &Vec<Row>) -> Result<Answer> {
+        Ok(part_2(input).into())
+    }
+}
+
+/// One line of the condition records
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    /// `#` damaged, `.` operational, `?` unknown
+    springs: String,
+    /// Sizes of each contiguous group of damaged springs, in order
+    counts: Vec<usize>,
+}
+
+fn part_1(rows: &[Row]) -> usize {
+    rows.par_iter()
+        .map(|row| count_arrangements::<usize>(&row.springs, &row.counts))
+        .sum()
+}
+
+/// Of course there's too many bits to fit in a u64 and you wouldn't want to do this exhaustively anyway
+/// is this actually greedy?
+fn part_2(rows: &[Row]) -> usize {
+    /*
+    return infile
+        .lines()
+        .filter_map(|s| s.split_once(' '))
+        // .map(|s| s.0.len())
+        .map(|s| s.0.chars().filter(|&c| c == '?').count() * 5)
+        .max()
+        .unwrap();
+    */
+    // ^^ number of bits to try exhaustively. About 45. Times the number of lines (another 10 bits' worth of multiplier).
+
+    /*
+    We want to sort of peek arbitrarily forward until the next `Operational` spring
+    if the next run of springs are Operational, we skip over them
+    if the next run of springs are `Unknown`, call its length U...
+    ... how many more Damaged springs can we match?
+    need sum(D) + count(D) to be <= U (roughly)
+    if previous match was Damaged (and fully used up) we will need the first Unknown to act as Operational
+
+    If we have a mixed run of D and U that's a nightmare and a half innit
+
+    If we work recursively we can maybe keep a lid on some of the madness?
+
+    Every multi-run of just D and U splits the world into up to 2^|multirun| possibilities?
+
+    For an arrangement to match, we need to have
+        - all unknowns allocated
+        - a matching number of D runs
+        - all D runs to be a matching length
+            - which implies that if a DU* chunk at the left edge can't be longer than its count, and a U*D chunk at the right edge similar
+
+    We can split into D|U chunks?
+        - if more chunks than counts, that's a fail
+        - if as many chunks as counts, then all that's left to do is potentially slide U around where it sits on an edge
+        - if fewer chunks than counts we may be able to split by allocating some U as O
+
+    This isn't greedy or whatever hey, maybe not even dynamic?
+    Maybe it is dynamic.
+
+    Subproblem: if we have a D of length A, bounded by Ux and Uy, and a count of length B >= A, how many arrangements are there?
+        - We're fitting 4 to ??###????, say
+        - B=4, A=3, X = 2, Y = 4
+        - well it can't slide further left or right than (B - A) = 1
+        - So it has 2 positions
+        - Now suppose A = 2: ??##????
+        - Now it can slide 2 left or 2 right. ****????, ?****????, ??****??
+        - OK, and now suppose A = 1 and Ux = 3 so as not to constrain: ???#????
+            - could be ****???? or ?****??? or ??****?? or ???****?
+        - This has consistently been 1 + B - A provided that min(Ux, Uy) >= B - A
+
+    Now let's consider Ux = 0 (wlog, Uy = 0 also). B is *anchored*: ##????? becomes ****????. Only one arrangement. This is as greedy as it gets.
+
+    Now let's consider e.g. B of length 3 in U (bounded on both sides by O) of length...
+        - 3 -> 1
+        - 4 -> 2
+        - 5 -> 3
+        - ... |arrangements| = U - B
+
+
+    But all of these are very simple.
+
+    Consider two single Ds in a run of eg 5 U (bounded on both sides by O)
+    Now, the Ds mustn't touch, so we really have the following options
+        - UDODU
+        - DOODU or UDOOD
+        - DOOOD (if there were 3 single Ds, this would be the only option really)
+
+
+    Maybe we can work in from the outside? We know that if we have O*D on the left or DO* on the right, then we have to match the corresponding edge greedily.
+
+    Suppose we had damages of count A, B, C to pack into U{> A+B+C+3} Then all arrangements are of the form:
+            O*D{A}O+D{B}O+D{C}O*
+
+    Subproblem: If we can take a single multichunk then we can divide and conquer a bit (multiplying the number of sub-arrangements at the end)
+        If we eat multiple counts that's more recursion?
+
+
+    Starts with O*D+U* and as long as |D|+|U| > leftmost we have a match (possibly with carryover)
+
+    If a thing starts with O*U+D+ and |U| < |leftmost| then that D also locates the leftmost count (and v/v on the right)
+        (Possibly splitting some U to the right)
+        (# of possibilities somewhere between 0 and 1+|leftmost|-|D|)
+
+    Starts with O*U*O and |U| < leftmost then that U is all Os (possibly a big win: # of possibilities === 1)
+
+    O*U+O and |U| == leftmost gives us exactly two possibilities (there or not) plus a recursion for our trouble
+
+    Starts with O*U+ ... and |U| > leftmost, that's where things get really tricky
+
+    OK SO: it's been like 5 hours. Reddit says this is one for Dynamic. Ugh.
+
+    Let's consider, again, the concept of multichunking, where a chunk is a {D,U}+ sequence (bounded by O or end of string)
+    Our *decision*, knowing how many runs of damaged springs we have left to go, and something about our posiion in the chunk list, is how many to allocate to *this* chunk.
+    Our *coordinates* are *probably* our index in the string generally and something based on our remaining runs
+    Our *return* is the return from this chunk, multiplied by the highest return from our "future chunks"
+
+    {(StrIndex, RunIndex): Arrangements}
+
+    Nice.
+
+    If we're at position X in the string, then we need to try and match U*D+U to the start of the string, where the run of the Us on the left is in 0..=Y
+    where Y is {remaining length of string} - {sum (future |D|)} + {count(future |D|)} - |D|
+    ... what? Well, all our remaining spring runs need a certain amount of space: the length of all those springs, plus a spacer in between
+    ... this implies that our *next* spring-run has to be in a bounded position relative to the start of the string
+    ... so we can try all of them, (immediately discarding any with an O in the way)
+    ... and then that's our coordinates for memoisation, too
+
+    */
+
+    rows.par_iter()
+        .map(|r| unfold(r, 5, "?"))
+        .map(|r| count_arrangements::<usize>(&r.springs, &r.counts))
+        .sum()
+}
+
+/// For each row, the condition records show every spring and whether it is operational (.) or damaged (#).
+/// This is the part of the condition records that is itself damaged; for some springs, it is simply unknown (?) whether the spring is operational or damaged.
+///
+/// After the list of springs for a given row, the size of each contiguous group of damaged springs is listed in the order those groups appear in the row.
+/// groups are always separated by at least one operational spring: #### would always be 4, never 2,2
+///
+/// Some rows have several possible arrangements
+///
+/// Anyway, we need
+fn parse_rows(infile: &str) -> Result<Vec<Row>, ParseError> {
+    infile
+        .lines()
+        .map(|line| parse_line(infile, line))
+        .collect()
+}
+
+fn parse_line(infile: &str, line: &str) -> Result<Row, ParseError> {
+    let (springs, nums) = line
+        .split_once(' ')
+        .ok_or_else(|| ParseError::new(infile, &line[line.len()..], "a space, then counts"))?;
+
+    if let Some((i, c)) = springs.char_indices().find(|(_, c)| !"#.?".contains(*c)) {
+        let found = &springs[i..i + c.len_utf8()];
+        return Err(ParseError::new(infile, found, "a spring (#, . or ?)"));
+    }
+
+    let counts = nums
+        .split(',')
+        .map(|n| n.parse().map_err(|_| ParseError::new(infile, n, "a count")))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Row {
+        springs: springs.to_string(),
+        counts,
+    })
+}
+
+/// `n` copies of the springs joined by `separator`, and `n` copies of the counts.
+/// Part 2 is five copies, separated by unknowns.
+pub fn unfold(row: &Row, n: usize, separator: &str) -> Row {
+    Row {
+        springs: vec![row.springs.as_str(); n].join(separator),
+        counts: row.counts.repeat(n),
+    }
+}
+
+/// The arrangements of every row, unfolded `n` times
+pub fn count_unfolded(rows: &[Row], n: usize, separator: &str) -> BigUint {
+    rows.par_iter()
+        .map(|row| {
+            let r = unfold(row, n, separator);
+            count_arrangements::<BigUint>(&r.springs, &r.counts)
+        })
+        .sum()
+}
+
+/// How a row's arrangements grow as it's unfolded more and more
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Growth {
+    /// The number of arrangements unfolded once, twice, ...
+    pub counts: Vec<BigUint>,
+    /// The common ratio, if `counts` is a geometric progression (and long enough to say)
+    pub ratio: Option<Ratio<BigUint>>,
+}
+
+impl Growth {
+    /// Unfold `row` by each of `1..=max_n`
+    pub fn of(row: &Row, max_n: usize, separator: &str) -> Self {
+        let counts: Vec<BigUint> = (1..=max_n)
+            .map(|n| count_unfolded(std::slice::from_ref(row), n, separator))
+            .collect();
+
+        let ratios: Option<Vec<Ratio<BigUint>>> = counts
+            .windows(2)
+            .map(|w| (!w[0].is_zero()).then(|| Ratio::new(w[1].clone(), w[0].clone())))
+            .collect();
+        let ratio = ratios
+            .filter(|rs| rs.len() >= 2 && rs.iter().all_equal())
+            .map(|rs| rs[0].clone());
+
+        Self { counts, ratio }
+    }
+}
+
+impl fmt::Display for Growth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.ratio, self.counts.first()) {
+            (Some(r), Some(a)) => write!(f, "{a} × ({r})^(N-1)"),
+            _ => write!(f, "not geometric: {}", self.counts.iter().join(", ")),
+        }
+    }
+}
+
+/** Actually do part 2!
+
+This started out as a memoised recursion (credit to /u/pendejadas,
+https://www.reddit.com/r/adventofcode/comments/18ge41g/2023_day_12_solutions/kd1adh1/)
+over (position in the springs, groups placed so far). Every one of those gets
+visited sooner or later, so we may as well fill them all in, from the right.
+
+From position `i`, with group `g` next, we can:
+  - leave spring `i` working, unless it's known-damaged
+  - start group `g` at `i`, so long as none of the springs it covers are
+    known-working, and the one just after it (if any) isn't known-damaged.
+    Then we carry on after that gap.
+
+With every group placed, there's one way to finish: if nothing left is known-damaged.
+
+Counting the known springs up to each position makes each of those checks O(1),
+so the whole table is O(springs × groups).
+**/
+pub(crate) struct WaysTable<N> {
+    /// springs + 1 columns (the last for having got to the end)
+    width: usize,
+    /// groups + 1 rows (the last for having placed them all)
+    ways: Vec<N>,
+}
+
+impl<N: Clone + Zero + One + AddAssign> WaysTable<N> {
+    pub(crate) fn new(springs: &str, counts: &[usize]) -> Self {
+        let springs = springs.as_bytes();
+        let width = springs.len() + 1;
+
+        // how many of each known condition there are before each position
+        let prefix = |c: u8| -> Vec<usize> {
+            let mut out = Vec::with_capacity(width);
+            out.push(0);
+            for &s in springs {
+                out.push(out[out.len() - 1] + usize::from(s == c));
+            }
+            out
+        };
+        let (damaged, working) = (prefix(b'#'), prefix(b'.'));
+        let none_of = |known: &[usize], r: std::ops::Range<usize>| known[r.end] == known[r.start];
+
+        let mut ways = vec![N::zero(); width * (counts.len() + 1)];
+        let last = counts.len() * width;
+        for i in 0..width {
+            if none_of(&damaged, i..springs.len()) {
+                ways[last + i] = N::one();
+            }
+        }
+
+        for (g, &size) in counts.iter().enumerate().rev() {
+            let (row, next_row) = (g * width, (g + 1) * width);
+            for i in (0..springs.len()).rev() {
+                let mut total = N::zero();
+                if springs[i] != b'#' {
+                    total += ways[row + i + 1].clone();
+                }
+                let end = i + size;
+                if end <= springs.len()
+                    && none_of(&working, i..end)
+                    && springs.get(end) != Some(&b'#')
+                {
+                    // step over the gap after the group, if there's room for one
+                    total += ways[next_row + springs.len().min(end + 1)].clone();
+                }
+                ways[row + i] = total;
+            }
+        }
+        Self { width, ways }
+    }
+
+    /// How many ways there are to finish from spring `line_idx` with `counts_idx` groups placed
+    pub(crate) fn get(&self, line_idx: usize, counts_idx: usize) -> &N {
+        &self.ways[counts_idx * self.width + line_idx]
+    }
+}
+
+/// How many arrangements of `springs` have groups of `counts`
+fn count_arrangements<N: Clone + Zero + One + AddAssign>(springs: &str, counts: &[usize]) -> N {
+    WaysTable::<N>::new(springs, counts).get(0, 0).clone()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const _ALL_KNOWN: &str = r"#.#.### 1,1,3
+.#...#....###. 1,1,3
+.#.###.#.###### 1,3,1,6
+####.#...#... 4,1,1
+#....######..#####. 1,6,5
+.###.##....# 3,2,1";
+
+    const EXAMPLE_1: &str = r"???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+
+    #[test]
+    fn test_parse_1() {
+        let row = parse_rows("???.### 1,1,3").unwrap().remove(0);
+        assert_eq!(row.springs, "???.###");
+        assert_eq!(row.counts, vec![1, 1, 3]);
+    }
+
+    #[test]
+    fn bad_spring() {
+        let e = parse_rows("???.### 1,1,3\n.?x. 1").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 3, "x"));
+    }
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(&parse_rows(EXAMPLE_1).unwrap()), 21);
+    }
+
+    #[test]
+    fn part_2_5x() {
+        assert_eq!(
+            unfold(&parse_rows(".# 1").unwrap().remove(0), 5, "?"),
+            parse_rows(".#?.#?.#?.#?.# 1,1,1,1,1").unwrap().remove(0)
+        );
+    }
+
+    #[test]
+    fn unfold_further() {
+        let rows = parse_rows(EXAMPLE_1).unwrap();
+        assert_eq!(count_unfolded(&rows, 1, "?"), BigUint::from(21u32));
+        assert_eq!(count_unfolded(&rows, 5, "?"), BigUint::from(525152u32));
+        // copies split by a working spring don't affect one another
+        assert_eq!(
+            count_unfolded(&rows, 2, "."),
+            BigUint::from(1u32 + 16 + 1 + 1 + 16 + 100)
+        );
+
+        // well past what fits in a u64
+        let big = count_unfolded(&rows[5..], 20, "?");
+        assert_eq!(big, BigUint::from(10u32) * BigUint::from(15u32).pow(19));
+    }
+
+    #[test]
+    fn growth() {
+        let rows = parse_rows(EXAMPLE_1).unwrap();
+        let last = Growth::of(&rows[5], 5, "?");
+        assert_eq!(last.ratio, Some(Ratio::from(BigUint::from(15u32))));
+        assert_eq!(last.to_string(), "10 × (15)^(N-1)");
+
+        let first = Growth::of(&rows[0], 5, "?");
+        assert_eq!(first.to_string(), "1 × (1)^(N-1)");
+
+        let row = parse_rows("??? 1").unwrap().remove(0);
+        let g = Growth::of(&row, 4, "?");
+        assert_eq!(g.counts, [3u32, 15, 84, 495].map(BigUint::from));
+        assert_eq!(g.ratio, None);
+        assert_eq!(g.to_string(), "not geometric: 3, 15, 84, 495");
+
+        assert_eq!(
+            Growth::of(&rows[5], 2, "?").ratio,
+            None,
+            "too short to tell"
+        );
+    }
+
+    /// Try every way of filling in the unknowns
+    fn brute_force(springs: &str, counts: &[usize]) -> usize {
+        let unknowns = springs.matches('?').count();
+        (0..1 << unknowns)
+            .filter(|bits| {
+                let mut k = 0;
+                let filled: String = springs
+                    .chars()
+                    .map(|c| match c {
+                        '?' => {
+                            k += 1;
+                            if bits >> (k - 1) & 1 == 1 {
+                                '#'
+                            } else {
+                                '.'
+                            }
+                        }
+                        c => c,
+                    })
+                    .collect();
+                let groups: Vec<usize> = filled
+                    .split('.')
+                    .filter(|g| !g.is_empty())
+                    .map(str::len)
+                    .collect();
+                groups == counts
+            })
+            .count()
+    }
+
+    #[test]
+    fn table_matches_brute_force() {
+        let counts: [&[usize]; 7] = [&[], &[1], &[2], &[3], &[1, 1], &[2, 1], &[1, 1, 1]];
+        for len in 0..=6 {
+            for springs in (0..len).map(|_| "#.?".chars()).multi_cartesian_product() {
+                let springs: String = springs.into_iter().collect();
+                for c in counts {
+                    assert_eq!(
+                        count_arrangements::<usize>(&springs, c),
+                        brute_force(&springs, c),
+                        "{springs} {c:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn memo_equiv() {
+        assert_eq!(count_arrangements::<usize>("?###????????", &[3, 2, 1]), 10)
+    }
+
+    #[test]
+    /// Base case needs to consider what happens when the RHS is anchored
+    /// All the analysis was good for something?
+    fn memo_right_anchor() {
+        assert_eq!(count_arrangements::<usize>("....???##?", &[3]), 2);
+    }
+
+    #[test]
+    fn test_pt2_print() {
+        assert_eq!(part_2(&parse_rows(".#.#. 1,1").unwrap()), 1)
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(&parse_rows(EXAMPLE_1).unwrap()), 525152);
+    }
+}
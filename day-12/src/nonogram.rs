@@ -0,0 +1,285 @@
+//! Picross: every row and every column of a picture has a clue like a day 12
+//! row's counts, and every cell is either filled (`#`) or empty (`.`).
+//!
+//! The clues come as two blocks separated by a blank line, rows then columns,
+//! one clue per line as comma-separated group sizes (`0` for an empty line):
+//!
+//! ```text
+//! 1,1
+//! 3
+//!
+//! 2
+//! 1
+//! 2
+//! ```
+
+use aoc_common::ParseError;
+use mapgrid::{Coord, DenseGrid};
+use num::{BigUint, Zero};
+
+use super::count_arrangements;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nonogram {
+    pub rows: Vec<Vec<usize>>,
+    pub cols: Vec<Vec<usize>>,
+}
+
+/// How a nonogram came out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Picture {
+    Solved(DenseGrid<char>),
+    /// Two of the pictures which fit (there may be more)
+    Ambiguous(DenseGrid<char>, DenseGrid<char>),
+    Impossible,
+}
+
+impl Nonogram {
+    pub fn parse(infile: &str) -> Result<Self, ParseError> {
+        let mut blocks = vec![vec![]];
+        for line in infile.lines() {
+            match line.trim() {
+                "" if !blocks[blocks.len() - 1].is_empty() => blocks.push(vec![]),
+                "" => {}
+                _ => blocks.last_mut().unwrap().push(line),
+            }
+        }
+        blocks.retain(|b| !b.is_empty());
+
+        let clues = |block: &[&str]| -> Result<Vec<Vec<usize>>, ParseError> {
+            block
+                .iter()
+                .map(|line| {
+                    line.split(',')
+                        .map(|n| {
+                            n.trim()
+                                .parse()
+                                .map_err(|_| ParseError::new(infile, n, "a group size"))
+                        })
+                        .filter(|n| !matches!(n, Ok(0)))
+                        .collect()
+                })
+                .collect()
+        };
+
+        match &blocks[..] {
+            [rows, cols] => Ok(Self {
+                rows: clues(rows)?,
+                cols: clues(cols)?,
+            }),
+            _ => {
+                let found = blocks.get(2).map_or(&infile[infile.len()..], |b| b[0]);
+                Err(ParseError::new(
+                    infile,
+                    found,
+                    "row clues, a blank line, then column clues",
+                ))
+            }
+        }
+    }
+
+    /// The clues which `picture` would have
+    pub fn of(picture: &DenseGrid<char>) -> Self {
+        let groups = |line: String| -> Vec<usize> {
+            line.split('.')
+                .filter(|g| !g.is_empty())
+                .map(str::len)
+                .collect()
+        };
+        Self {
+            rows: picture.rows().map(|r| groups(r.iter().collect())).collect(),
+            cols: (0..picture.width())
+                .map(|c| groups(column(picture, c).into_iter().collect()))
+                .collect(),
+        }
+    }
+
+    pub fn solve(&self) -> Picture {
+        let mut found = vec![];
+        let blank = DenseGrid::new(self.cols.len(), self.rows.len(), '?');
+        self.search(blank, &mut found);
+
+        let mut found = found.into_iter();
+        match (found.next(), found.next()) {
+            (Some(a), Some(b)) => Picture::Ambiguous(a, b),
+            (Some(a), None) => Picture::Solved(a),
+            _ => Picture::Impossible,
+        }
+    }
+
+    /// Settle what we can, then guess at the first cell we can't, until we've
+    /// found two pictures (or run out of guesses)
+    fn search(&self, mut grid: DenseGrid<char>, found: &mut Vec<DenseGrid<char>>) {
+        if !self.propagate(&mut grid) {
+            return;
+        }
+        let Some(guess) = grid.iter().find(|(_, v)| **v == '?').map(|(k, _)| k) else {
+            found.push(grid);
+            return;
+        };
+        for v in ['#', '.'] {
+            if found.len() >= 2 {
+                return;
+            }
+            let mut next = grid.clone();
+            next[guess] = v;
+            self.search(next, found);
+        }
+    }
+
+    /// Settle every row and column in turn until nothing changes.
+    /// False if some line can't be filled in at all.
+    fn propagate(&self, grid: &mut DenseGrid<char>) -> bool {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (r, clue) in self.rows.iter().enumerate() {
+                let line: Vec<char> = grid.rows().nth(r).unwrap().to_vec();
+                let Some(settled) = settle(&line, clue) else {
+                    return false;
+                };
+                for (c, v) in settled.into_iter().enumerate() {
+                    let k = Coord::new(r as isize, c as isize);
+                    changed |= grid[k] != v;
+                    grid[k] = v;
+                }
+            }
+            for (c, clue) in self.cols.iter().enumerate() {
+                let Some(settled) = settle(&column(grid, c), clue) else {
+                    return false;
+                };
+                for (r, v) in settled.into_iter().enumerate() {
+                    let k = Coord::new(r as isize, c as isize);
+                    changed |= grid[k] != v;
+                    grid[k] = v;
+                }
+            }
+        }
+        true
+    }
+}
+
+fn column(grid: &DenseGrid<char>, c: usize) -> Vec<char> {
+    grid.rows().map(|row| row[c]).collect()
+}
+
+/// Fill in the unknowns of `line` which every arrangement fitting `clue` agrees on,
+/// if any arrangement fits at all
+fn settle(line: &[char], clue: &[usize]) -> Option<Vec<char>> {
+    // a wide enough line has more arrangements than fit in a u128 (from about
+    // 130 unknown cells), and nothing stops a nonogram being that wide
+    let count =
+        |line: &[char]| count_arrangements::<BigUint>(&line.iter().collect::<String>(), clue);
+
+    let total = count(line);
+    if total.is_zero() {
+        return None;
+    }
+    let mut out = line.to_vec();
+    for i in (0..line.len()).filter(|&i| line[i] == '?') {
+        let mut filled = line.to_vec();
+        filled[i] = '#';
+        out[i] = match count(&filled) {
+            n if n.is_zero() => '.',
+            n if n == total => '#',
+            _ => '?',
+        };
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod test {
+    use mapgrid::Grid;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    const HEART: &str = "\
+1,1
+5
+5
+3
+1
+
+3
+3
+4
+3
+3
+";
+
+    #[test]
+    fn solve_by_propagation() {
+        let puzzle = Nonogram::parse(HEART).unwrap();
+        assert_eq!(
+            puzzle.rows,
+            [vec![1, 1], vec![5], vec![5], vec![3], vec![1]]
+        );
+
+        let Picture::Solved(picture) = puzzle.solve() else {
+            panic!("should have a unique solution");
+        };
+        assert_eq!(picture.visualise(), "#...#\n#####\n#####\n.###.\n..#..\n");
+        assert_eq!(Nonogram::of(&picture), puzzle);
+    }
+
+    #[test]
+    fn ambiguous_and_impossible() {
+        // either diagonal
+        let puzzle = Nonogram::parse("1\n1\n\n1\n1").unwrap();
+        let Picture::Ambiguous(a, b) = puzzle.solve() else {
+            panic!("should be ambiguous");
+        };
+        assert_ne!(a, b);
+        assert_eq!(Nonogram::of(&a), puzzle);
+        assert_eq!(Nonogram::of(&b), puzzle);
+
+        let puzzle = Nonogram::parse("2\n0\n\n1\n0").unwrap();
+        assert_eq!(puzzle.solve(), Picture::Impossible);
+    }
+
+    #[test]
+    fn random_pictures() {
+        let mut rng = StdRng::seed_from_u64(20);
+        for _ in 0..50 {
+            let mut picture = DenseGrid::new(7, 6, '.');
+            for r in 0..6 {
+                for c in 0..7 {
+                    if rng.gen_bool(0.5) {
+                        picture[Coord::new(r, c)] = '#';
+                    }
+                }
+            }
+            let puzzle = Nonogram::of(&picture);
+            match puzzle.solve() {
+                Picture::Solved(p) => assert_eq!(p, picture),
+                Picture::Ambiguous(a, b) => {
+                    assert_ne!(a, b);
+                    assert_eq!(Nonogram::of(&a), puzzle);
+                    assert_eq!(Nonogram::of(&b), puzzle);
+                }
+                Picture::Impossible => panic!("{puzzle:?} came from a picture"),
+            }
+        }
+    }
+
+    #[test]
+    fn wide_lines() {
+        // C(158, 42) arrangements, just more than a u128 can count
+        let line = vec!['?'; 199];
+        assert_eq!(settle(&line, &[1; 42]), Some(line.clone()));
+
+        // just room for them all
+        let settled: String = settle(&line[..83], &[1; 42]).unwrap().into_iter().collect();
+        assert_eq!(settled, "#.".repeat(41) + "#");
+    }
+
+    #[test]
+    fn bad_clues() {
+        let e = Nonogram::parse("1\nx\n\n1").unwrap_err();
+        assert_eq!((e.line, e.found.as_str()), (2, "x"));
+        assert!(Nonogram::parse("1\n1").is_err());
+        assert!(Nonogram::parse("1\n\n1\n\n1").is_err());
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use aoc_common::{Answer, ParseError, Solution};
+use mapgrid::{Coord, DenseGrid, Direction, Direction::*};
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = DenseGrid<char>;
+
+    fn parse(infile: &str) -> Result<DenseGrid<char>> {
+        Ok(parse_grid(infile)?)
+    }
+    fn part_1(input: &DenseGrid<char>) -> Result<Answer> {
+        Ok(part_1(input).into())
+    }
+    fn part_2(input: &DenseGrid<char>) -> Result<Answer> {
+        Ok(part_2(input).into())
+    }
+}
+
+/// `O` rolls, `#` is fixed, `.` is empty
+fn parse_grid(infile: &str) -> Result<DenseGrid<char>, ParseError> {
+    DenseGrid::parse_with(
+        infile,
+        |c| "O#.".contains(c).then_some(c),
+        "a rock (O or #) or .",
+    )
+}
+
+fn part_1(grid: &DenseGrid<char>) -> usize {
+    // functionally equivalent to segmenting within each column
+    // then summing (row, row-1, ...) for as many O as within that segment
+    let width = grid.width();
+    let height = grid.height();
+
+    let mut total = 0;
+
+    for c in 0..width {
+        let mut score = height;
+        for r in 0..height {
+            match grid[Coord::new(r as isize, c as isize)] {
+                '#' => score = (height - r).saturating_sub(1),
+                'O' => {
+                    total += score;
+                    score -= 1;
+                }
+                _ => {}
+            }
+            // print!("{} ({r}, {c}): {total}\t", grid[Coord::new(r as isize, c as isize)]);
+        }
+        // println!("");
+    }
+    total
+}
+
+/// Now we have to do it properly.
+/// We do have a trick though: this (hopefully) settles into being cyclic.
+/// Suppose we had to do 25 cycles with a lead in of 3 and a period of 8.
+///     ...XxxxxxxxXxxxxxxxXxxxxx
+///             ^---- equiv-----^   
+fn part_2(grid: &DenseGrid<char>) -> usize {
+    // {grid : (cycle, score)}
+    let mut cache: HashMap<DenseGrid<char>, (usize, usize)> = HashMap::new();
+
+    let mut grid = grid.clone();
+
+    for cycles in 1..=1_000_000_000 {
+        spin_cycle(&mut grid);
+        let score = score_grid(&grid);
+        if let Some((pcycle, _pscore)) = cache.get(&grid) {
+            let period = cycles - pcycle;
+            let lead_in = pcycle;
+            return cache
+                .values()
+                .filter(|(c, _)| *c == lead_in + ((1_000_000_000 - cycles) % period))
+                .map(|(_, s)| *s)
+                .next()
+                .unwrap();
+        }
+        cache.insert(grid.clone(), (cycles, score));
+    }
+    score_grid(&grid)
+}
+
+/// The load on the north beams as the rocks lie, without tilting
+fn score_grid(grid: &DenseGrid<char>) -> usize {
+    let height = grid.height();
+    let mut total = 0;
+    for (r, row) in grid.rows().enumerate() {
+        total += (height - r) * row.iter().filter(|&&c| c == 'O').count();
+    }
+    total
+}
+
+/// Tilt north, then west, then south, then east
+fn spin_cycle(grid: &mut DenseGrid<char>) {
+    for dir in [North, West, South, East] {
+        tilt(grid, dir);
+    }
+}
+
+/// Roll every `O` as far towards `dir` as it goes.
+///
+/// We walk each line back from the edge it rolls to, keeping track of the
+/// nearest free cell: a `#` moves that to just behind it, and an `O` fills it.
+fn tilt(grid: &mut DenseGrid<char>, dir: Direction) {
+    let (height, width) = (grid.height() as isize, grid.width() as isize);
+    let edge: Vec<Coord> = match dir {
+        North => (0..width).map(|c| Coord::new(0, c)).collect(),
+        South => (0..width).map(|c| Coord::new(height - 1, c)).collect(),
+        West => (0..height).map(|r| Coord::new(r, 0)).collect(),
+        East => (0..height).map(|r| Coord::new(r, width - 1)).collect(),
+    };
+    let back = dir.opposite().to_delta();
+
+    for start in edge {
+        let mut free = start;
+        let mut k = start;
+        while grid.contains(k) {
+            match grid[k] {
+                '#' => free = k + back,
+                'O' => {
+                    grid[k] = '.';
+                    grid[free] = 'O';
+                    free += back;
+                }
+                _ => {}
+            }
+            k += back;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mapgrid::Grid as _;
+
+    /// O can roll in any of the four cardinal directions, # are fixed, . are empty
+    const EXAMPLE_1: &str = r"O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(&parse_grid(EXAMPLE_1).unwrap()), 136);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(&parse_grid(EXAMPLE_1).unwrap()), 64);
+    }
+
+    #[test]
+    fn bad_tile() {
+        let e = parse_grid("O..\n.x#\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 2, "x"));
+    }
+
+    fn grid_to_string(grid: &[Vec<char>]) -> String {
+        let mut out = String::new();
+        for row in grid {
+            for cha in row {
+                out.push(*cha);
+            }
+            out.push('\n')
+        }
+        out.pop();
+        out
+    }
+
+    fn transpose(grid: Vec<Vec<char>>) -> Vec<Vec<char>> {
+        let width = grid.iter().map(|r| r.len()).min().unwrap_or(0);
+        let height = grid.len();
+
+        let mut out = vec![vec!['~'; height]; width];
+
+        for (row, line) in grid.iter().enumerate() {
+            for (col, cha) in line.iter().enumerate() {
+                out[col][row] = *cha;
+            }
+        }
+        out
+    }
+
+    fn mirror_ew(grid: Vec<Vec<char>>) -> Vec<Vec<char>> {
+        grid.into_iter()
+            .map(move |mut s| {
+                s.reverse();
+                s
+            })
+            .collect()
+    }
+
+    #[test]
+    fn transforms() {
+        let input = vec![
+            vec!['1', '2', '3'],
+            vec!['4', '5', '6'],
+            vec!['7', '8', '9'],
+        ];
+
+        assert_eq!(input.clone(), transpose(transpose(input.clone())));
+        assert_eq!(input.clone(), mirror_ew(mirror_ew(input.clone())));
+    }
+
+    #[test]
+    fn deser() {
+        assert_eq!(
+            parse_grid(EXAMPLE_1).unwrap().visualise(),
+            format!("{EXAMPLE_1}\n")
+        );
+    }
+
+    #[test]
+    fn score() {
+        let n_tilt = r"OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#....";
+        assert_eq!(part_1(&parse_grid(n_tilt).unwrap()), 136);
+    }
+    #[test]
+    fn score_2() {
+        let n_tilt = r"OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#....";
+        assert_eq!(score_grid(&parse_grid(n_tilt).unwrap()), 136);
+    }
+
+    #[test]
+    fn what_do() {
+        let input = vec![
+            vec!['1', '2', '3'],
+            vec!['4', '5', '6'],
+            vec!['7', '8', '9'],
+        ];
+
+        println!("I\n{}", grid_to_string(&input.clone()));
+        println!("M\n{}", grid_to_string(&mirror_ew(input.clone())));
+        println!("T\n{}", grid_to_string(&transpose(input.clone())));
+
+        println!(
+            "MT = rotate east\n{}",
+            grid_to_string(&mirror_ew(transpose(input.clone())))
+        );
+
+        println!(
+            "TMT = flip NS\n{}",
+            grid_to_string(&transpose(mirror_ew(transpose(input.clone()))))
+        );
+
+        println!(
+            "MTMT = rotate south \n{}",
+            grid_to_string(&mirror_ew(transpose(mirror_ew(transpose(input.clone())))))
+        );
+        // intermediate stage
+        println!(
+            "TMTMT\n{}",
+            grid_to_string(&transpose(mirror_ew(transpose(mirror_ew(transpose(
+                input.clone()
+            ))))))
+        );
+        println!(
+            "MTMTMT = rotate west\n{}",
+            grid_to_string(&mirror_ew(transpose(mirror_ew(transpose(mirror_ew(
+                transpose(input.clone())
+            ))))))
+        );
+        println!(
+            "MTMTMTMT = I\n{}",
+            grid_to_string(&mirror_ew(transpose(mirror_ew(transpose(mirror_ew(
+                transpose(mirror_ew(transpose(input.clone())))
+            ))))))
+        );
+        println!(
+            "TM = rotate west?\n{}",
+            grid_to_string(&transpose(mirror_ew(input.clone())))
+        );
+    }
+
+    #[test]
+    fn one_cycle() {
+        let mut grid = parse_grid(EXAMPLE_1).unwrap();
+        println!("{EXAMPLE_1}");
+
+        tilt(&mut grid, North);
+        println!("-> N\n{}", grid.visualise());
+
+        tilt(&mut grid, West);
+        println!("-> W\n{}", grid.visualise());
+
+        tilt(&mut grid, South);
+        println!("-> S\n{}", grid.visualise());
+
+        tilt(&mut grid, East);
+        println!("-> E\n{}", grid.visualise());
+
+        let one = r".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....";
+
+        assert_eq!(grid.visualise(), format!("{one}\n"));
+    }
+
+    #[test]
+    fn three_cycle() {
+        let three = r".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#...O###.O
+#.OOO#...O";
+
+        let mut grid = parse_grid(EXAMPLE_1).unwrap();
+        for _ in 0..3 {
+            spin_cycle(&mut grid);
+        }
+
+        assert_eq!(grid.visualise(), format!("{three}\n"));
+    }
+}
@@ -0,0 +1,140 @@
+use anyhow::Result;
+use aoc_common::{Answer, ParseError, Solution};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = Vec<Step>;
+
+    fn parse(infile: &str) -> Result<Vec<Step>> {
+        Ok(parse_steps(infile)?)
+    }
+    fn part_1(input: &Vec<Step>) -> Result<Answer> {
+        Ok(part_1(input).into())
+    }
+    fn part_2(input: &Vec<Step>) -> Result<Answer> {
+        Ok(part_2(input).into())
+    }
+}
+
+/// One step of the initialisation sequence: `rn=1` or `cm-`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    /// The step as written, which is what part 1 hashes
+    text: String,
+    label: String,
+    /// The focal length of the lens to put in, or `None` to take it out
+    focal: Option<usize>,
+}
+
+fn parse_steps(infile: &str) -> Result<Vec<Step>, ParseError> {
+    infile
+        .trim()
+        .split(',')
+        .map(|text| {
+            let (label, focal) = match text.strip_suffix('-') {
+                Some(label) => (label, None),
+                None => {
+                    let (label, focal) = text.split_once('=').ok_or_else(|| {
+                        ParseError::new(infile, text, "a step like `rn=1` or `cm-`")
+                    })?;
+                    let focal = focal
+                        .parse()
+                        .map_err(|_| ParseError::new(infile, focal, "a focal length"))?;
+                    (label, Some(focal))
+                }
+            };
+            Ok(Step {
+                text: text.to_string(),
+                label: label.to_string(),
+                focal,
+            })
+        })
+        .collect()
+}
+
+fn part_1(steps: &[Step]) -> usize {
+    steps.iter().map(|step| hash(&step.text)).sum()
+}
+
+fn hash(input: &str) -> usize {
+    let mut out = 0;
+
+    for c in input.chars() {
+        out += c as usize;
+        out *= 17;
+        out %= 256;
+    }
+    out
+}
+
+fn part_2(steps: &[Step]) -> usize {
+    // so we're implementing a hashmap with linear probing, right?
+
+    let mut boxes: Vec<Vec<Entry>> = vec![vec![]; 256];
+
+    for Step { label, focal, .. } in steps {
+        let addr = hash(label);
+        match *focal {
+            None => boxes[addr].retain(|f| f.key != *label),
+            Some(focal) => {
+                // check if existing
+                let mut done = false;
+                for e in &mut boxes[addr] {
+                    if e.key == *label {
+                        e.value = focal;
+                        done = true;
+                    }
+                }
+                if !done {
+                    boxes[addr].push(Entry {
+                        key: label.to_string(),
+                        value: focal,
+                    });
+                }
+            }
+        }
+        // println!("{lens} :\n{boxes:?}");
+    }
+
+    let mut total = 0;
+    for (box_num, boxn) in boxes.iter().enumerate() {
+        for (slot_num, lens) in boxn.iter().enumerate() {
+            total += (1 + box_num) * (1 + slot_num) * lens.value;
+        }
+    }
+    total
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    key: String,
+    value: usize,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r"rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(&parse_steps(EXAMPLE_1).unwrap()), 1320);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(&parse_steps(EXAMPLE_1).unwrap()), 145);
+    }
+
+    #[test]
+    fn bad_steps() {
+        let e = parse_steps("rn=1,cm?,qp=3").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (1, 6, "cm?"));
+
+        let e = parse_steps("rn=1,cm=x\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (1, 9, "x"));
+    }
+}
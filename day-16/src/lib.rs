@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use aoc_common::{Answer, ParseError, Solution};
+use mapgrid::{
+    Coord, DenseGrid,
+    Direction::{self, *},
+};
+use rayon::prelude::*;
+
+use strum::{self, Display, EnumString};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input = DenseGrid<Tile>;
+
+    fn parse(infile: &str) -> Result<DenseGrid<Tile>> {
+        Ok(parse_grid(infile)?)
+    }
+    fn part_1(input: &DenseGrid<Tile>) -> Result<Answer> {
+        Ok(part_1(input).into())
+    }
+    fn part_2(input: &DenseGrid<Tile>) -> Result<Answer> {
+        Ok(part_2(input).into())
+    }
+}
+
+fn parse_grid(infile: &str) -> Result<DenseGrid<Tile>, ParseError> {
+    DenseGrid::parse_with(
+        infile,
+        |c| Tile::try_from(c).ok(),
+        "a tile (., /, \\, | or -)",
+    )
+}
+
+fn part_1(grid: &DenseGrid<Tile>) -> usize {
+    raytrace(grid, Coord::new(0, 0), East)
+}
+
+/// Do some ray tracing!
+/// Note: if the beam *starts* on a block which would change its direction, its direction should be changed.
+/// (A previous implementation didn't do this...)
+fn raytrace(grid: &DenseGrid<Tile>, start_pos: Coord, start_dir: Direction) -> usize {
+    // Now that we have our grid, it's time to raytrace.
+
+    // (Position, Direction)
+    let mut done: HashSet<(Coord, Direction)> = HashSet::new();
+    let mut stack: Vec<(Coord, Direction)> = Vec::new();
+
+    // This was a VecDeque queue, but a stack works just as well as a queue here, and is slightly faster
+    stack.push((start_pos, start_dir));
+
+    while let Some((pos, dir)) = stack.pop() {
+        if done.contains(&(pos, dir)) {
+            continue;
+        }
+
+        let Some(tile) = grid.get(pos) else {
+            continue;
+        };
+        let go = |d: Direction| (pos + d.to_delta(), d);
+
+        match (tile, dir) {
+            (Tile::Empty, _) => stack.push(go(dir)),
+            // `/` swaps north with east, and south with west
+            (Tile::MirrorF, North | South) => stack.push(go(dir.turn_right())),
+            (Tile::MirrorF, East | West) => stack.push(go(dir.turn_left())),
+            // `\` swaps north with west, and south with east
+            (Tile::MirrorB, North | South) => stack.push(go(dir.turn_left())),
+            (Tile::MirrorB, East | West) => stack.push(go(dir.turn_right())),
+            (Tile::SplitterV, North | South) | (Tile::SplitterH, East | West) => {
+                stack.push(go(dir))
+            }
+            (Tile::SplitterV, East | West) | (Tile::SplitterH, North | South) => {
+                stack.push(go(dir.turn_left()));
+                stack.push(go(dir.turn_right()));
+            }
+        }
+
+        done.insert((pos, dir));
+
+        // if done.len() > 5 {
+        //     break;
+        // }
+    }
+
+    // let mut done_vis = vec![vec!['.'; grid[0].len()]; grid.len()];
+
+    // for (pos, _dir) in done.iter() {
+    //     done_vis[pos[0] as usize][pos[1] as usize] = '#'
+    // }
+
+    // for (row, orig) in done_vis.iter().zip(infile.lines()) {
+    //     print!("{}", join(row, ""));
+    //     println!("\t{orig}")
+    // }
+    let energised: HashSet<Coord> = done.iter().map(|(p, _)| *p).collect();
+
+    energised.len()
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, EnumString, Display)]
+pub enum Tile {
+    #[strum(serialize = ".")]
+    Empty,
+    #[strum(serialize = "/")]
+    MirrorF,
+    #[strum(serialize = "\\")]
+    MirrorB,
+    #[strum(serialize = "|")]
+    SplitterV,
+    #[strum(serialize = "-")]
+    SplitterH,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = char;
+    fn try_from(c: char) -> std::result::Result<Tile, char> {
+        use Tile::*;
+        match c {
+            '.' => Ok(Empty),
+            '/' => Ok(MirrorF),
+            '\\' => Ok(MirrorB),
+            '|' => Ok(SplitterV),
+            '-' => Ok(SplitterH),
+            _ => Err(c),
+        }
+    }
+}
+
+/// Just Brute Force It
+/// (even in debug mode it only takes like 12 seconds)
+fn part_2(grid: &DenseGrid<Tile>) -> usize {
+    let rmax = grid.height() as isize;
+    let cmax = grid.width() as isize;
+    // println!("Trying {} starts...", (rmax + cmax) * 2);
+
+    let left = (0..rmax)
+        .into_par_iter()
+        .map(|r| raytrace(grid, Coord::new(r, 0), East))
+        .max()
+        .unwrap_or_default();
+    let right = (0..rmax)
+        .into_par_iter()
+        .map(|r| raytrace(grid, Coord::new(r, cmax - 1), West))
+        .max()
+        .unwrap_or_default();
+    let down = (0..cmax)
+        .into_par_iter()
+        .map(|c| raytrace(grid, Coord::new(0, c), South))
+        .max()
+        .unwrap_or_default();
+    let up = (0..cmax)
+        .into_par_iter()
+        .map(|c| raytrace(grid, Coord::new(rmax - 1, c), North))
+        .max()
+        .unwrap_or_default();
+
+    left.max(right).max(down).max(up)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(&parse_grid(EXAMPLE_1).unwrap()), 46);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(&parse_grid(EXAMPLE_1).unwrap()), 51);
+    }
+}
+
+/*
+- First attempt at actual for 1 got 8406, which is too high
+- Maybe starting on a / means itchanges direction straight away?
+- Yep. (This lead to a substantial revision of part 1 code).
+
+Part 2 is a simple factoring-out and generalisation of part 1 (into `raytrace`)
+then brute forcing that for all the possible start positions.
+There's only about 440 on the real input.
+
+This took 12 seconds in debug mode on my machine, and about 0.3s in release with rayon
+
+(But much  more than 12s to put rayon in and compile in release mode!)
+*/
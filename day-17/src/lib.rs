@@ -0,0 +1,444 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{bail, Context, Result};
+use aoc_common::{
+    search::{astar, Found, SearchState},
+    Answer, ParseError, Solution,
+};
+use mapgrid::{
+    Coord, DenseGrid,
+    Direction::{self, *},
+};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Input = DenseGrid<u32>;
+
+    fn parse(infile: &str) -> Result<DenseGrid<u32>> {
+        Ok(parse_grid(infile)?)
+    }
+    fn part_1(input: &DenseGrid<u32>) -> Result<Answer> {
+        part_1(input).map(Answer::from)
+    }
+    fn part_2(input: &DenseGrid<u32>) -> Result<Answer> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+/// Heat loss per block, row-major
+fn parse_grid(infile: &str) -> Result<DenseGrid<u32>, ParseError> {
+    DenseGrid::parse_with(infile, |c| c.to_digit(10), "a digit")
+}
+
+/// position, number of steps taken so far, in Direction
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point {
+    pub pos: Coord,
+    pub run: usize,
+    pub dir: Direction,
+    /// How many of the course's waypoints we've called in at
+    pub leg: usize,
+}
+
+/// How a crucible may move
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct MovementRules {
+    /// How far it must go in a straight line before it can turn, or stop
+    pub min_run: usize,
+    /// How far it can go in a straight line before it must turn
+    pub max_run: usize,
+    /// Whether it may turn right round (which counts as a turn)
+    pub can_reverse: bool,
+    /// Extra heat lost on every turn
+    pub turn_cost: u32,
+}
+
+impl MovementRules {
+    /// Part 1
+    pub const CRUCIBLE: MovementRules = MovementRules {
+        min_run: 1,
+        max_run: 3,
+        can_reverse: false,
+        turn_cost: 0,
+    };
+
+    /// Part 2
+    pub const ULTRA_CRUCIBLE: MovementRules = MovementRules {
+        min_run: 4,
+        max_run: 10,
+        can_reverse: false,
+        turn_cost: 0,
+    };
+}
+
+/// Where a crucible may start, must call in on the way (in order), and may finish
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Course {
+    pub sources: Vec<Coord>,
+    pub waypoints: Vec<Coord>,
+    pub targets: Vec<Coord>,
+}
+
+impl Course {
+    /// From the top left to the bottom right, as in the puzzle
+    pub fn corners(grid: &DenseGrid<u32>) -> Self {
+        let end = Coord::new(grid.height() as isize - 1, grid.width() as isize - 1);
+        Self {
+            sources: vec![Coord::new(0, 0)],
+            waypoints: vec![],
+            targets: vec![end],
+        }
+    }
+}
+
+/// Cells of `grid` given as `ROW,COL`, where either can be `*` for all of them,
+/// and negative numbers count back from the far edge (so `*,-1` is the right-hand column).
+pub fn select_cells<V>(grid: &DenseGrid<V>, spec: &str) -> Result<Vec<Coord>> {
+    let (row, col) = spec
+        .split_once(',')
+        .with_context(|| format!("{spec:?} isn't ROW,COL"))?;
+
+    let axis = |s: &str, len: usize| -> Result<Vec<isize>> {
+        let len = len as isize;
+        if s.trim() == "*" {
+            return Ok((0..len).collect());
+        }
+        let n: isize = s
+            .trim()
+            .parse()
+            .with_context(|| format!("{s:?} in {spec:?}"))?;
+        let n = if n < 0 { n + len } else { n };
+        if !(0..len).contains(&n) {
+            bail!("{s} is off the grid in {spec:?}");
+        }
+        Ok(vec![n])
+    };
+    let rows = axis(row, grid.height())?;
+    let cols = axis(col, grid.width())?;
+
+    Ok(rows
+        .iter()
+        .flat_map(|&r| cols.iter().map(move |&c| Coord::new(r, c)))
+        .collect())
+}
+
+/// The city, and how the crucible may move through it
+struct City<'a> {
+    grid: &'a DenseGrid<u32>,
+    rules: MovementRules,
+    waypoints: &'a [Coord],
+    targets: HashSet<Coord>,
+}
+
+impl City<'_> {
+    /// Call in at as many waypoints as we're at (in case some are repeated)
+    fn arrive(&self, mut p: Point) -> Point {
+        while self.waypoints.get(p.leg) == Some(&p.pos) {
+            p.leg += 1;
+        }
+        p
+    }
+}
+
+impl SearchState<City<'_>> for Point {
+    type Cost = u32;
+
+    fn successors(&self, city: &City) -> impl IntoIterator<Item = Point> {
+        let rules = city.rules;
+        // - no reversing, unless the rules say so
+        // - can't go straight on for more than max_run
+        // - can't turn until min_run (or at all before the first step)
+        Direction::ALL
+            .into_iter()
+            .filter(move |&d| rules.can_reverse || d != self.dir.opposite())
+            .filter(move |&d| match d == self.dir {
+                true => self.run < rules.max_run,
+                false => self.run >= rules.min_run.max(1),
+            })
+            .map(|d| Point {
+                pos: self.pos + d.to_delta(),
+                run: if d == self.dir { self.run + 1 } else { 1 },
+                dir: d,
+                leg: self.leg,
+            })
+            .filter(|p| city.grid.contains(p.pos))
+            .map(|p| city.arrive(p))
+    }
+
+    /// We lose the heat of each block we enter, and maybe some for turning
+    fn cost(&self, next: &Point, city: &City) -> u32 {
+        let turn = match next.dir == self.dir {
+            true => 0,
+            false => city.rules.turn_cost,
+        };
+        city.grid[next.pos] + turn
+    }
+
+    fn is_goal(&self, city: &City) -> bool {
+        self.leg == city.waypoints.len()
+            && self.run >= city.rules.min_run
+            && city.targets.contains(&self.pos)
+    }
+}
+
+/// A route round a course
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Itinerary {
+    pub path: Vec<Point>,
+    pub total: u32,
+    /// The heat lost getting to each waypoint from the last, then on to the finish
+    pub legs: Vec<u32>,
+    /// How many states the search looked at
+    pub expanded: usize,
+}
+
+/// The route round `course` losing least heat, if there is one.
+///
+/// This is one search (not one per leg), as how the crucible arrives at a
+/// waypoint constrains how it can leave.
+pub fn route(grid: &DenseGrid<u32>, rules: MovementRules, course: &Course) -> Option<Itinerary> {
+    let city = City {
+        grid,
+        rules,
+        waypoints: &course.waypoints,
+        targets: course.targets.iter().copied().collect(),
+    };
+    let cheapest = grid.iter().map(|(_, v)| *v).min().unwrap_or_default();
+
+    // We don't count the starting block, and haven't moved in any direction yet
+    let starts = course.sources.iter().flat_map(|&pos| {
+        Direction::ALL.map(|dir| {
+            city.arrive(Point {
+                pos,
+                run: 0,
+                dir,
+                leg: 0,
+            })
+        })
+    });
+
+    // every block left to go costs at least the cheapest block
+    let to_go = |p: &Point| {
+        let nearest = course.targets.iter().map(|&t| (t - p.pos).norm1()).min();
+        nearest.unwrap_or_default() as u32 * cheapest
+    };
+
+    let Found {
+        path,
+        cost,
+        expanded,
+    } = astar(&city, starts, to_go)?;
+
+    let mut legs = vec![0; course.waypoints.len() + 1];
+    for w in path.windows(2) {
+        legs[w[0].leg] += w[0].cost(&w[1], &city);
+    }
+
+    Some(Itinerary {
+        path,
+        total: cost,
+        legs,
+        expanded,
+    })
+}
+
+/// The heat loss map with the route drawn over it, as in the puzzle:
+/// each block entered shows which way the crucible was going.
+pub fn render_route(grid: &DenseGrid<u32>, path: &[Point]) -> String {
+    // the start block wasn't entered, so keeps its digit
+    let steps: HashMap<Coord, Direction> = path.iter().skip(1).map(|p| (p.pos, p.dir)).collect();
+
+    let mut out = String::new();
+    for (k, heat) in grid.iter() {
+        match steps.get(&k) {
+            Some(North) => out.push('^'),
+            Some(East) => out.push('>'),
+            Some(South) => out.push('v'),
+            Some(West) => out.push('<'),
+            None => out.push_str(&heat.to_string()),
+        }
+        if k.col as usize == grid.width() - 1 {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn part_1(grid: &DenseGrid<u32>) -> Result<usize> {
+    let found = route(grid, MovementRules::CRUCIBLE, &Course::corners(grid))
+        .context("no route to the bottom right")?;
+    Ok(found.total as usize)
+}
+
+fn part_2(grid: &DenseGrid<u32>) -> Result<usize> {
+    let found = route(grid, MovementRules::ULTRA_CRUCIBLE, &Course::corners(grid))
+        .context("no route to the bottom right")?;
+    Ok(found.total as usize)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r"2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(&parse_grid(EXAMPLE_1).unwrap()).unwrap(), 102);
+    }
+
+    const SIMPLE: &str = r"111
+991
+121
+199
+191
+121
+991";
+
+    #[test]
+    fn part_1_simple() {
+        assert_eq!(part_1(&parse_grid(SIMPLE).unwrap()).unwrap(), 14);
+    }
+
+    #[test]
+    fn part_2_example_1() {
+        assert_eq!(part_2(&parse_grid(EXAMPLE_1).unwrap()).unwrap(), 94);
+    }
+    const EXAMPLE_2: &str = r"111111111111
+999999999991
+999999999991
+999999999991
+999999999991";
+
+    #[test]
+    fn part_2_example_2() {
+        assert_eq!(part_2(&parse_grid(EXAMPLE_2).unwrap()).unwrap(), 71);
+    }
+
+    #[test]
+    fn render() {
+        let grid = parse_grid(EXAMPLE_1).unwrap();
+        let corners = Course::corners(&grid);
+        let found = route(&grid, MovementRules::ULTRA_CRUCIBLE, &corners).unwrap();
+        assert_eq!(
+            render_route(&grid, &found.path),
+            "2>>>>>>>>1323
+32154535v5623
+32552456v4254
+34465858v5452
+45466578v>>>>
+143859879845v
+445787698776v
+363787797965v
+465496798688v
+456467998645v
+122468686556v
+254654888773v
+432267465553v
+"
+        );
+    }
+
+    #[test]
+    fn route_is_consistent() {
+        let grid = parse_grid(EXAMPLE_1).unwrap();
+        let corners = Course::corners(&grid);
+        let found = route(&grid, MovementRules::CRUCIBLE, &corners).unwrap();
+        let path = &found.path;
+        assert_eq!(path[0].pos, Coord::new(0, 0));
+        assert_eq!(path.last().unwrap().pos, Coord::new(12, 12));
+        for w in path.windows(2) {
+            assert_eq!(w[1].pos - w[0].pos, w[1].dir.to_delta());
+            assert!(w[1].run <= 3);
+        }
+        let heat: u32 = path[1..].iter().map(|p| grid[p.pos]).sum();
+        assert_eq!(heat, found.total);
+    }
+
+    #[test]
+    fn variants() {
+        let grid = parse_grid(EXAMPLE_1).unwrap();
+        let corners = Course::corners(&grid);
+        let rules = |min_run, max_run, can_reverse, turn_cost| MovementRules {
+            min_run,
+            max_run,
+            can_reverse,
+            turn_cost,
+        };
+        let turns = |path: &[Point]| {
+            path[1..]
+                .windows(2)
+                .filter(|w| w[0].dir != w[1].dir)
+                .count()
+        };
+
+        assert_eq!(
+            route(&grid, rules(1, 12, false, 0), &corners)
+                .unwrap()
+                .total,
+            78
+        );
+        assert_eq!(
+            route(&grid, rules(2, 5, false, 0), &corners).unwrap().total,
+            101
+        );
+        // doubling back lets it break up a long run
+        assert_eq!(
+            route(&grid, rules(1, 3, true, 0), &corners).unwrap().total,
+            101
+        );
+
+        // a big enough penalty and it'll turn only the once
+        let found = route(&grid, rules(1, 12, false, 100), &corners).unwrap();
+        assert_eq!((found.total, turns(&found.path)), (179, 1));
+        let found = route(&grid, rules(1, 3, false, 5), &corners).unwrap();
+        assert_eq!((found.total, turns(&found.path)), (159, 11));
+    }
+
+    #[test]
+    fn courses() {
+        let grid = parse_grid(EXAMPLE_1).unwrap();
+
+        // it can't stop where it starts, so has to go one block down
+        let course = Course {
+            sources: select_cells(&grid, "0,*").unwrap(),
+            waypoints: vec![],
+            targets: select_cells(&grid, "*,-1").unwrap(),
+        };
+        let found = route(&grid, MovementRules::CRUCIBLE, &course).unwrap();
+        assert_eq!(found.total, 3);
+        assert_eq!(found.path.last().unwrap().pos, Coord::new(1, 12));
+
+        // round the other two corners first
+        let course = Course {
+            waypoints: vec![Coord::new(12, 0), Coord::new(0, 12)],
+            ..Course::corners(&grid)
+        };
+        let found = route(&grid, MovementRules::CRUCIBLE, &course).unwrap();
+        assert_eq!(found.legs, [53, 94, 67]);
+        assert_eq!(found.total, 214);
+    }
+
+    #[test]
+    fn cells() {
+        let grid = parse_grid(EXAMPLE_2).unwrap();
+        assert_eq!(select_cells(&grid, "0,*").unwrap().len(), 12);
+        assert_eq!(select_cells(&grid, "-1, -2").unwrap(), [Coord::new(4, 10)]);
+        assert!(select_cells(&grid, "5,0").is_err());
+        assert!(select_cells(&grid, "0").is_err());
+    }
+}
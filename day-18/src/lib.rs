@@ -0,0 +1,277 @@
+use std::{collections::HashSet, ops::RangeInclusive};
+
+use anyhow::{Context, Result};
+use aoc_common::{
+    search::{flood, SearchState},
+    Answer, ParseError, Solution,
+};
+use mapgrid::{polygon, Coord};
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Input = Vec<Dig>;
+
+    fn parse(infile: &str) -> Result<Vec<Dig>> {
+        Ok(parse_plan(infile)?)
+    }
+    fn part_1(input: &Vec<Dig>) -> Result<Answer> {
+        Ok(part_1(input).into())
+    }
+    fn part_2(input: &Vec<Dig>) -> Result<Answer> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+/// One line of the dig plan: `R 6 (#70c710)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dig {
+    /// [row, col] of a single step
+    dir: [isize; 2],
+    run: isize,
+    /// The colour is really the instruction we should have followed (part 2)
+    colour: Option<([isize; 2], isize)>,
+}
+
+fn parse_plan(infile: &str) -> Result<Vec<Dig>, ParseError> {
+    infile
+        .lines()
+        .filter(|s| !s.trim().is_empty())
+        .map(|line| {
+            let mut words = line.split_whitespace();
+            let missing = |what| ParseError::new(infile, &line[line.len()..], what);
+
+            let d = words.next().ok_or_else(|| missing("a direction"))?;
+            let dir = match d {
+                "U" => [-1, 0],
+                "D" => [1, 0],
+                "L" => [0, -1],
+                "R" => [0, 1],
+                _ => return Err(ParseError::new(infile, d, "a direction (U, D, L or R)")),
+            };
+
+            let r = words.next().ok_or_else(|| missing("a distance"))?;
+            let run = r
+                .parse()
+                .map_err(|_| ParseError::new(infile, r, "a distance"))?;
+
+            let colour = match words.next() {
+                None => None,
+                Some(c) => Some(
+                    parse_colour(c)
+                        .ok_or_else(|| ParseError::new(infile, c, "a colour, like (#70c710)"))?,
+                ),
+            };
+
+            if let Some(extra) = words.next() {
+                return Err(ParseError::new(infile, extra, "the end of the line"));
+            }
+
+            Ok(Dig { dir, run, colour })
+        })
+        .collect()
+}
+
+/// `(#70c710)` is 0x70c71 steps in direction 0 (right)
+fn parse_colour(colour: &str) -> Option<([isize; 2], isize)> {
+    let hex = colour.strip_prefix("(#")?.strip_suffix(')')?;
+    // checking they're all hex digits first means each is one byte, so we can slice
+    if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let run = isize::from_str_radix(&hex[0..5], 16).ok()?;
+    let dir = match &hex[5..] {
+        "0" => [0, 1],
+        "1" => [1, 0],
+        "2" => [0, -1],
+        "3" => [-1, 0],
+        _ => return None,
+    };
+    Some((dir, run))
+}
+
+fn part_1(dig_list: &[Dig]) -> usize {
+    let mut coords: HashSet<[isize; 2]> = HashSet::new();
+
+    let mut r: isize = 0;
+    let mut c: isize = 0;
+    coords.insert([0, 0]);
+
+    for dig in dig_list {
+        let [dr, dc] = dig.dir;
+        for _ in 0..dig.run {
+            r += dr;
+            c += dc;
+            coords.insert([r, c]);
+        }
+    }
+
+    let rmin = coords.iter().map(|[r, _]| *r).min().unwrap_or(0);
+    let rmax = coords.iter().map(|[r, _]| *r).max().unwrap_or(0);
+    let cmin = coords.iter().map(|[_, c]| *c).min().unwrap_or(0);
+    let cmax = coords.iter().map(|[_, c]| *c).max().unwrap_or(0);
+
+    // if I had a way to get a point which was guaranteed to be inside the polygon, I could do a flood fill...
+    // (if there isn't one, the trench encloses nothing, and we're done)
+    if let Some(start_point) = find_point_in_polygon(&coords) {
+        let trench = Trench {
+            dug: &coords,
+            rows: rmin..=rmax,
+            cols: cmin..=cmax,
+        };
+        coords.extend(flood(&trench, [start_point]).into_keys());
+    }
+
+    coords.len()
+}
+
+/// Digging out the inside of the trench, within its bounding box
+struct Trench<'a> {
+    dug: &'a HashSet<[isize; 2]>,
+    rows: RangeInclusive<isize>,
+    cols: RangeInclusive<isize>,
+}
+
+impl SearchState<Trench<'_>> for [isize; 2] {
+    type Cost = usize;
+
+    fn successors(&self, trench: &Trench) -> impl IntoIterator<Item = [isize; 2]> {
+        let [r, c] = *self;
+        (-1..=1)
+            .flat_map(move |i| (-1..=1).map(move |j| [r + i, c + j]))
+            .filter(|[r, c]| trench.rows.contains(r) && trench.cols.contains(c))
+            .filter(|n| !trench.dug.contains(n))
+    }
+
+    fn cost(&self, _: &[isize; 2], _: &Trench) -> usize {
+        1
+    }
+
+    fn is_goal(&self, _: &Trench) -> bool {
+        false
+    }
+}
+
+/// Finds a point in a polygon by iteration over the bounding box
+/// Returned point is guaranteed to not be on the edge
+fn find_point_in_polygon(polygon: &HashSet<[isize; 2]>) -> Option<[isize; 2]> {
+    let rmin = polygon.iter().map(|[r, _]| *r).min().unwrap_or(0);
+    let rmax = polygon.iter().map(|[r, _]| *r).max().unwrap_or(0);
+    let cmin = polygon.iter().map(|[_, c]| *c).min().unwrap_or(0);
+    let cmax = polygon.iter().map(|[_, c]| *c).max().unwrap_or(0);
+
+    for r in rmin + 1..rmax {
+        for c in cmin + 1..cmax {
+            if is_point_in_polygon([r, c], polygon) && !polygon.contains(&[r, c]) {
+                return Some([r, c]);
+            }
+        }
+    }
+    None
+}
+
+/// Calculate the number of times a scanline crosses to the right
+/// (Should be odd if it's inside)
+fn is_point_in_polygon(point: [isize; 2], polygon: &HashSet<[isize; 2]>) -> bool {
+    let cmax = polygon.iter().map(|[_, c]| *c).max().unwrap_or(0);
+    // let cmin = polygon.iter().map(|[_, c]| *c).min().unwrap_or(0);
+
+    let r = point[0];
+    let mut count = 0;
+    for c in point[1] + 1..=cmax + 1 {
+        if polygon.contains(&[r, c]) && !polygon.contains(&[r, c - 1]) {
+            count += 1;
+        }
+    }
+    count % 2 == 1
+}
+
+fn part_2(dig_list: &[Dig]) -> Result<usize> {
+    // now our scanline approach will be much too slow
+    // Perhaps the Shoelace Formula will prove useful?
+    // We even have the points in a specific order around the perimeter!
+    // for (x_i, y_i) the total area is equal to 0.5 * sum(i in 1..=n; (y_i * (x_{i-1} - x_{i+1})))
+
+    let mut points: Vec<[isize; 2]> = vec![[0, 0]];
+
+    let mut r = 0;
+    let mut c = 0;
+    for (n, dig) in dig_list.iter().enumerate() {
+        let ([i, j], x) = dig
+            .colour
+            .with_context(|| format!("dig {} has no colour", n + 1))?;
+        r += i * x;
+        c += j * x;
+        points.push([r, c]);
+    }
+    // circularisation not needed
+    let vertices: Vec<Coord> = points.into_iter().map(Coord::from).collect();
+
+    // The shoelace formula assumes zero edge width, but our trench is a whole cube wide.
+    // Think of the points as the centres of cubes: then Pick's theorem counts the cubes
+    // strictly inside, and the ones on the edge are dug too.
+    Ok(polygon::interior_points(&vertices) + polygon::boundary_points(&vertices))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r"R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    /// empty top issue
+    const SCANLINE_TEST: &str = "U 10
+R 4
+D 4
+R 4
+U 4
+R 4
+D 10
+L 12";
+
+    #[test]
+    fn bad_direction() {
+        let e = parse_plan("R 6 (#70c710)\nX 5 (#0dc571)").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 1, "X"));
+
+        // six bytes, but not six hex digits
+        let e = parse_plan("R 6 (#1234é)").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (1, 5, "(#1234é)"));
+    }
+
+    #[test]
+    fn nothing_inside() {
+        assert_eq!(part_1(&parse_plan("R 2\nL 2").unwrap()), 3);
+    }
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(&parse_plan(EXAMPLE_1).unwrap()), 62);
+    }
+    #[test]
+    fn part_1_scanline() {
+        assert_eq!(part_1(&parse_plan(SCANLINE_TEST).unwrap()), 131);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(
+            part_2(&parse_plan(EXAMPLE_1).unwrap()).unwrap(),
+            952408144115
+        );
+    }
+}
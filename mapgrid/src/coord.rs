@@ -0,0 +1,204 @@
+use std::ops::{Add, AddAssign, Div, Mul, Neg, RangeInclusive, Sub, SubAssign};
+
+/// The rows and columns a grid covers, as given by [`Grid::bounds`](crate::Grid::bounds)
+pub type Bounds = [RangeInclusive<isize>; 2];
+
+/// A position on a grid, or a step between two. Rows grow downwards (south).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Coord {
+    pub row: isize,
+    pub col: isize,
+}
+
+impl Coord {
+    pub const fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+
+    pub fn within(self, bounds: &Bounds) -> bool {
+        bounds[0].contains(&self.row) && bounds[1].contains(&self.col)
+    }
+
+    /// Manhattan length
+    pub fn norm1(self) -> usize {
+        self.row.unsigned_abs() + self.col.unsigned_abs()
+    }
+
+    /// North, east, south and west of here, so long as they're within `bounds`
+    pub fn neighbours4(self, bounds: &Bounds) -> impl Iterator<Item = Coord> + '_ {
+        Direction::ALL
+            .into_iter()
+            .map(move |d| self + d.to_delta())
+            .filter(|n| n.within(bounds))
+    }
+
+    /// As [`Coord::neighbours4`], and the diagonals too, clockwise from north
+    pub fn neighbours8(self, bounds: &Bounds) -> impl Iterator<Item = Coord> + '_ {
+        Direction::ALL
+            .into_iter()
+            .flat_map(|d| [d.to_delta(), d.to_delta() + d.turn_right().to_delta()])
+            .map(move |delta| self + delta)
+            .filter(|n| n.within(bounds))
+    }
+}
+
+impl From<[isize; 2]> for Coord {
+    fn from([row, col]: [isize; 2]) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<Coord> for [isize; 2] {
+    fn from(k: Coord) -> Self {
+        [k.row, k.col]
+    }
+}
+
+impl Add for Coord {
+    type Output = Coord;
+    fn add(self, rhs: Coord) -> Coord {
+        Coord::new(self.row + rhs.row, self.col + rhs.col)
+    }
+}
+
+impl AddAssign for Coord {
+    fn add_assign(&mut self, rhs: Coord) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Coord {
+    type Output = Coord;
+    fn sub(self, rhs: Coord) -> Coord {
+        Coord::new(self.row - rhs.row, self.col - rhs.col)
+    }
+}
+
+impl SubAssign for Coord {
+    fn sub_assign(&mut self, rhs: Coord) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<isize> for Coord {
+    type Output = Coord;
+    fn mul(self, n: isize) -> Coord {
+        Coord::new(self.row * n, self.col * n)
+    }
+}
+
+/// Rounds towards zero, like integer division does
+impl Div<isize> for Coord {
+    type Output = Coord;
+    fn div(self, n: isize) -> Coord {
+        Coord::new(self.row / n, self.col / n)
+    }
+}
+
+impl Neg for Coord {
+    type Output = Coord;
+    fn neg(self) -> Coord {
+        Coord::new(-self.row, -self.col)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise from north
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Anticlockwise
+    pub fn turn_left(self) -> Direction {
+        use Direction::*;
+        match self {
+            North => West,
+            West => South,
+            South => East,
+            East => North,
+        }
+    }
+
+    /// Clockwise
+    pub fn turn_right(self) -> Direction {
+        self.turn_left().opposite()
+    }
+
+    pub fn opposite(self) -> Direction {
+        use Direction::*;
+        match self {
+            North => South,
+            South => North,
+            East => West,
+            West => East,
+        }
+    }
+
+    /// One step this way
+    pub fn to_delta(self) -> Coord {
+        use Direction::*;
+        match self {
+            North => Coord::new(-1, 0),
+            East => Coord::new(0, 1),
+            South => Coord::new(1, 0),
+            West => Coord::new(0, -1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Coord::new(3, -2);
+        let b = Coord::from([1, 5]);
+        assert_eq!(a + b, Coord::new(4, 3));
+        assert_eq!(a - b, Coord::new(2, -7));
+        assert_eq!(a * 2, Coord::new(6, -4));
+        assert_eq!(a / 2, Coord::new(1, -1));
+        assert_eq!(-a, Coord::new(-3, 2));
+        assert_eq!(<[isize; 2]>::from(a), [3, -2]);
+        assert_eq!((a - b).norm1(), 9);
+    }
+
+    #[test]
+    fn directions() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.opposite());
+            assert_eq!(d.opposite().to_delta(), -d.to_delta());
+        }
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+    }
+
+    #[test]
+    fn neighbours() {
+        let bounds = [0..=2, 0..=3];
+        let middle = Coord::new(1, 1);
+        assert_eq!(middle.neighbours4(&bounds).count(), 4);
+        assert_eq!(middle.neighbours8(&bounds).count(), 8);
+
+        let corner = Coord::new(0, 3);
+        assert_eq!(
+            corner.neighbours4(&bounds).collect::<Vec<_>>(),
+            [Coord::new(1, 3), Coord::new(0, 2)]
+        );
+        assert_eq!(
+            corner.neighbours8(&bounds).collect::<Vec<_>>(),
+            [Coord::new(1, 3), Coord::new(1, 2), Coord::new(0, 2)]
+        );
+    }
+}
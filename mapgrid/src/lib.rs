@@ -1,101 +1,258 @@
-use std::cmp::{Eq, Ord, PartialEq, PartialOrd};
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
-    ops::{Add, RangeInclusive},
+    fmt::{Display, Write},
+    ops::{Index, IndexMut},
 };
 
-// #[derive(From, Into, PartialOrd, PartialEq, Ord, Eq)]
-pub type Coord = [isize; 2];
+use aoc_common::ParseError;
+
+mod coord;
+pub mod polygon;
+
+pub use coord::{Bounds, Coord, Direction};
 
 pub trait Grid<V> {
+    /// Cells where `f` gives `None` are left out (or, for a dense grid, defaulted)
     fn from_str_with<F: Fn(char) -> Option<V>>(input: &str, f: F) -> Self;
 
-    fn bounds(&self) -> [RangeInclusive<isize>; 2];
+    /// [rows, columns]
+    fn bounds(&self) -> Bounds;
 
+    /// One line per row, `.` for empty cells
     fn visualise(&self) -> String;
 }
-/*
-impl<V> Grid<V> for BTreeMap<Coord, V> {
-    fn from_str_with<F: Fn(char) -> Option<V>>(input: &str, f: F) -> BTreeMap<Coord, V> {
-        let mut out = BTreeMap::new();
-
-        for (row, s) in input.lines().enumerate() {
-            for (col, cha) in s.chars().enumerate() {
-                if let Some(v) = f(cha) {
-                    out.insert([row as isize, col as isize], v);
-                }
-            }
+
+/// Every character of `input`, with where it is
+fn cells(input: &str) -> impl Iterator<Item = (Coord, char)> + '_ {
+    input.lines().enumerate().flat_map(|(row, s)| {
+        s.chars()
+            .enumerate()
+            .map(move |(col, cha)| (Coord::new(row as isize, col as isize), cha))
+    })
+}
+
+fn bounds_of<'a>(coords: impl Iterator<Item = &'a Coord> + Clone) -> Bounds {
+    let rmax = coords.clone().map(|v| v.row).max().unwrap_or(0);
+    let cmax = coords.clone().map(|v| v.col).max().unwrap_or(0);
+    let rmin = coords.clone().map(|v| v.row).min().unwrap_or(0);
+    let cmin = coords.map(|v| v.col).min().unwrap_or(0);
+
+    [rmin..=rmax, cmin..=cmax]
+}
+
+/// Draw each cell in `bounds` with `f`
+fn draw(bounds: Bounds, f: impl Fn(Coord) -> String) -> String {
+    let [rows, cols] = bounds;
+    let mut out = String::new();
+
+    for r in rows {
+        for c in cols.clone() {
+            out.push_str(&f(Coord::new(r, c)));
         }
-        out
+        out.push('\n');
     }
+    out
 }
 
-impl<V> Grid<V> for BTreeSet<Coord> {
-    /// Note: the value of V is disregarded
-    fn from_str_with<F: Fn(char) -> Option<V>>(input: &str, f: F) -> BTreeSet<Coord> {
-        let mut out = BTreeSet::new();
+macro_rules! impl_set {
+    ($set:ident) => {
+        impl<V> Grid<V> for $set<Coord> {
+            /// Note: the value of V is disregarded
+            fn from_str_with<F: Fn(char) -> Option<V>>(input: &str, f: F) -> $set<Coord> {
+                cells(input)
+                    .filter(|(_, cha)| f(*cha).is_some())
+                    .map(|(coord, _)| coord)
+                    .collect()
+            }
 
-        for (row, s) in input.lines().enumerate() {
-            for (col, cha) in s.chars().enumerate() {
-                if f(cha).is_some() {
-                    out.insert([row as isize, col as isize]);
-                }
+            fn bounds(&self) -> Bounds {
+                bounds_of(self.iter())
+            }
+
+            fn visualise(&self) -> String {
+                draw(<Self as Grid<V>>::bounds(self), |k| {
+                    match self.contains(&k) {
+                        true => "#",
+                        false => ".",
+                    }
+                    .to_string()
+                })
             }
         }
-        out
-    }
+    };
 }
 
-impl<V> Grid<V> for HashMap<Coord, V> {
-    fn from_str_with<F: Fn(char) -> Option<V>>(input: &str, f: F) -> HashMap<Coord, V> {
-        let mut out = HashMap::new();
+macro_rules! impl_map {
+    ($map:ident) => {
+        impl<V: Display> Grid<V> for $map<Coord, V> {
+            fn from_str_with<F: Fn(char) -> Option<V>>(input: &str, f: F) -> $map<Coord, V> {
+                cells(input)
+                    .filter_map(|(coord, cha)| Some((coord, f(cha)?)))
+                    .collect()
+            }
+
+            fn bounds(&self) -> Bounds {
+                bounds_of(self.keys())
+            }
 
-        for (row, s) in input.lines().enumerate() {
-            for (col, cha) in s.chars().enumerate() {
-                if let Some(v) = f(cha) {
-                    out.insert([row as isize, col as isize], v);
-                }
+            fn visualise(&self) -> String {
+                draw(self.bounds(), |k| match self.get(&k) {
+                    Some(v) => v.to_string(),
+                    None => ".".to_string(),
+                })
             }
         }
-        out
-    }
+    };
 }
-*/
 
-impl<V> Grid<V> for HashSet<Coord> {
-    /// Note: the value of V is disregarded
-    fn from_str_with<F: Fn(char) -> Option<V>>(input: &str, f: F) -> HashSet<Coord> {
-        let mut out = HashSet::new();
+impl_set!(HashSet);
+impl_set!(BTreeSet);
+impl_map!(HashMap);
+impl_map!(BTreeMap);
+
+/// A rectangle with a value in every cell, stored row-major
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DenseGrid<V> {
+    cells: Vec<V>,
+    width: usize,
+    height: usize,
+}
+
+impl<V> DenseGrid<V> {
+    pub fn new(width: usize, height: usize, fill: V) -> Self
+    where
+        V: Clone,
+    {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
 
-        for (row, s) in input.lines().enumerate() {
-            for (col, cha) in s.chars().enumerate() {
-                if f(cha).is_some() {
-                    out.insert([row as isize, col as isize]);
+    /// The one grid parser: every character must be a cell, and every row the same width.
+    ///
+    /// `expected` describes what `f` accepts, for the error when it doesn't.
+    /// Blank lines are ignored.
+    pub fn parse_with<F: Fn(char) -> Option<V>>(
+        input: &str,
+        f: F,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for line in input.lines().filter(|l| !l.is_empty()) {
+            let mut count = 0;
+            for (i, cha) in line.char_indices() {
+                let v = f(cha).ok_or_else(|| {
+                    ParseError::new(input, &line[i..i + cha.len_utf8()], expected)
+                })?;
+                cells.push(v);
+                count += 1;
+            }
+            match width {
+                None => width = Some(count),
+                Some(w) if w != count => {
+                    let end = &line[line.len()..];
+                    return Err(ParseError::new(
+                        input,
+                        end,
+                        format!("a row {w} wide, like the first"),
+                    ));
                 }
+                Some(_) => {}
             }
+            height += 1;
         }
-        out
+
+        Ok(Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, k: Coord) -> bool {
+        self.offset(k).is_some()
+    }
+
+    pub fn get(&self, k: Coord) -> Option<&V> {
+        self.offset(k).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, k: Coord) -> Option<&mut V> {
+        self.offset(k).map(|i| &mut self.cells[i])
+    }
+
+    /// Every cell, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &V)> {
+        let width = self.width.max(1);
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, v)| (Coord::new((i / width) as isize, (i % width) as isize), v))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[V]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    fn offset(&self, k: Coord) -> Option<usize> {
+        let r = usize::try_from(k.row).ok().filter(|&r| r < self.height)?;
+        let c = usize::try_from(k.col).ok().filter(|&c| c < self.width)?;
+        Some(r * self.width + c)
+    }
+}
+
+/// Panics outside the grid
+impl<V> Index<Coord> for DenseGrid<V> {
+    type Output = V;
+
+    fn index(&self, k: Coord) -> &V {
+        self.get(k)
+            .unwrap_or_else(|| panic!("{k:?} is outside the grid"))
+    }
+}
+
+impl<V> IndexMut<Coord> for DenseGrid<V> {
+    fn index_mut(&mut self, k: Coord) -> &mut V {
+        self.get_mut(k)
+            .unwrap_or_else(|| panic!("{k:?} is outside the grid"))
+    }
+}
+
+impl<V: Default + Display> Grid<V> for DenseGrid<V> {
+    /// Cells `f` rejects are `V::default()`.
+    ///
+    /// Panics if the rows aren't all the same width: use [`DenseGrid::parse_with`]
+    /// to get that back as a [`ParseError`].
+    fn from_str_with<F: Fn(char) -> Option<V>>(input: &str, f: F) -> Self {
+        Self::parse_with(input, |c| Some(f(c).unwrap_or_default()), "a cell")
+            .unwrap_or_else(|e| panic!("{e}"))
     }
-    fn bounds(&self) -> [RangeInclusive<isize>; 2] {
-        let xmax = self.iter().map(|v| v[0]).max().unwrap_or(0);
-        let ymax = self.iter().map(|v| v[1]).max().unwrap_or(0);
-        let xmin = self.iter().map(|v| v[0]).min().unwrap_or(0);
-        let ymin = self.iter().map(|v| v[1]).min().unwrap_or(0);
 
-        [xmin..=xmax, ymin..=ymax]
+    fn bounds(&self) -> Bounds {
+        [
+            0..=(self.height as isize - 1),
+            0..=(self.width as isize - 1),
+        ]
     }
 
     fn visualise(&self) -> String {
         let mut out = String::new();
-        let [xs, ys] = <HashSet<[isize; 2]> as Grid<V>>::bounds(self);
-
-        for y in ys {
-            for x in xs.clone() {
-                if self.contains(&[x, y]) {
-                    out.push('#');
-                } else {
-                    out.push('.');
-                }
+        for row in self.rows() {
+            for v in row {
+                write!(out, "{v}").unwrap();
             }
             out.push('\n');
         }
@@ -104,6 +261,74 @@ impl<V> Grid<V> for HashSet<Coord> {
 }
 
 #[cfg(test)]
-mod tests {
+mod test {
     use super::*;
+
+    const EXAMPLE: &str = "#..\n.#.\n..#\n#..\n";
+
+    fn hash(c: char) -> Option<char> {
+        (c == '#').then_some(c)
+    }
+
+    #[test]
+    fn sparse_round_trip() {
+        // bounds are those of the cells present, so pad the example out to them
+        assert_eq!(
+            <HashSet<Coord> as Grid<()>>::visualise(&HashSet::from_str_with(EXAMPLE, |c| {
+                (c == '#').then_some(())
+            })),
+            EXAMPLE
+        );
+        assert_eq!(BTreeMap::from_str_with(EXAMPLE, hash).visualise(), EXAMPLE);
+        assert_eq!(HashMap::from_str_with(EXAMPLE, hash).visualise(), EXAMPLE);
+        assert_eq!(
+            BTreeMap::from_str_with(EXAMPLE, hash).bounds(),
+            [0..=3, 0..=2]
+        );
+    }
+
+    #[test]
+    fn dense() {
+        let grid = DenseGrid::parse_with(EXAMPLE, Some, "anything").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid[Coord::new(3, 0)], '#');
+        assert_eq!(grid[Coord::new(3, 1)], '.');
+        assert_eq!(grid.get(Coord::new(4, 0)), None);
+        assert_eq!(grid.get(Coord::new(0, -1)), None);
+        assert_eq!(grid.visualise(), EXAMPLE);
+        assert_eq!(grid.bounds(), [0..=3, 0..=2]);
+        assert_eq!(
+            grid.iter()
+                .filter(|(_, v)| **v == '#')
+                .map(|(k, _)| k)
+                .collect::<Vec<_>>(),
+            [[0, 0], [1, 1], [2, 2], [3, 0]].map(Coord::from)
+        );
+    }
+
+    #[test]
+    fn dense_errors() {
+        let e = DenseGrid::parse_with("#..\n.x.\n", hash_or_dot, "# or .").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 2, "x"));
+
+        let e = DenseGrid::parse_with("#..\n.#\n", hash_or_dot, "# or .").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+    }
+
+    #[test]
+    fn dense_from_str() {
+        // rejected cells are defaulted, but the grid is the same shape
+        let grid: DenseGrid<u8> = Grid::from_str_with(EXAMPLE, |c| (c == '#').then_some(1));
+        assert_eq!(grid.visualise(), "100\n010\n001\n100\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "a row 3 wide")]
+    fn dense_from_str_ragged() {
+        let _: DenseGrid<char> = Grid::from_str_with("#..\n.#\n", hash);
+    }
+
+    fn hash_or_dot(c: char) -> Option<char> {
+        "#.".contains(c).then_some(c)
+    }
 }
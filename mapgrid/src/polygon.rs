@@ -0,0 +1,114 @@
+//! Polygons whose corners are all grid points, such as a loop of cells: how
+//! much they enclose, by the shoelace formula and Pick's theorem.
+//!
+//! Vertices come in order round the polygon, and it closes back to the first.
+//! Any vertex may sit partway along a straight edge; every cell of a loop will do.
+
+use crate::Coord;
+
+/// Twice the signed area: positive if the vertices go clockwise as drawn
+/// (rows growing downwards), negative if anticlockwise
+pub fn double_area(vertices: &[Coord]) -> isize {
+    let next = vertices.iter().cycle().skip(1);
+    vertices
+        .iter()
+        .zip(next)
+        .map(|(a, b)| a.col * b.row - b.col * a.row)
+        .sum()
+}
+
+/// How many grid points lie on the edges
+pub fn boundary_points(vertices: &[Coord]) -> usize {
+    let next = vertices.iter().cycle().skip(1);
+    vertices
+        .iter()
+        .zip(next)
+        .map(|(&a, &b)| {
+            let step = b - a;
+            gcd(step.row.unsigned_abs(), step.col.unsigned_abs())
+        })
+        .sum()
+}
+
+/// How many grid points lie strictly inside.
+///
+/// Pick's theorem says area = interior + boundary / 2 - 1. That needs a simple
+/// polygon: where the edges double back over each other (say, out and straight
+/// back), they count twice towards the boundary, and the theorem can give less
+/// than nothing, so we stop at nothing.
+pub fn interior_points(vertices: &[Coord]) -> usize {
+    match double_area(vertices).unsigned_abs() {
+        0 => 0,
+        area => (area + 2).saturating_sub(boundary_points(vertices)) / 2,
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn poly(points: &[[isize; 2]]) -> Vec<Coord> {
+        points.iter().map(|&p| Coord::from(p)).collect()
+    }
+
+    #[test]
+    fn squares() {
+        let unit = poly(&[[0, 0], [0, 1], [1, 1], [1, 0]]);
+        assert_eq!(double_area(&unit), 2);
+        assert_eq!((boundary_points(&unit), interior_points(&unit)), (4, 0));
+
+        let mut square = poly(&[[0, 0], [0, 2], [2, 2], [2, 0]]);
+        assert_eq!((boundary_points(&square), interior_points(&square)), (8, 1));
+        square.reverse();
+        assert_eq!(double_area(&square), -8);
+        assert_eq!(interior_points(&square), 1);
+
+        // the same square, by every point on its edge
+        let cells = poly(&[
+            [0, 0],
+            [0, 1],
+            [0, 2],
+            [1, 2],
+            [2, 2],
+            [2, 1],
+            [2, 0],
+            [1, 0],
+        ]);
+        assert_eq!(double_area(&cells), 8);
+        assert_eq!((boundary_points(&cells), interior_points(&cells)), (8, 1));
+    }
+
+    #[test]
+    fn diagonals() {
+        // (1, 1), (1, 2) and (2, 1) are inside
+        let triangle = poly(&[[0, 0], [0, 4], [4, 0]]);
+        assert_eq!(double_area(&triangle), 16);
+        assert_eq!(boundary_points(&triangle), 12);
+        assert_eq!(interior_points(&triangle), 3);
+    }
+
+    #[test]
+    fn no_area() {
+        // out two steps and back again
+        let there_and_back = poly(&[[0, 0], [0, 2], [0, 0]]);
+        assert_eq!(double_area(&there_and_back), 0);
+        assert_eq!(boundary_points(&there_and_back), 4);
+        assert_eq!(interior_points(&there_and_back), 0);
+
+        assert_eq!(interior_points(&poly(&[[3, 4]])), 0);
+        assert_eq!(interior_points(&[]), 0);
+
+        // a unit square with a long spike out of one corner and back
+        let spiked = poly(&[[0, 0], [0, 10], [0, 0], [0, -1], [1, -1], [1, 0]]);
+        assert_eq!(double_area(&spiked), -2);
+        assert_eq!(boundary_points(&spiked), 24);
+        assert_eq!(interior_points(&spiked), 0);
+    }
+}
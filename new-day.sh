@@ -5,18 +5,24 @@
 daynum=$(( $(basename day-*/ | sed -e 's/day-//' | tail -n 1) + 1))
 
 printf -v newday -- 'day-%02d' "$daynum"
+newcrate=${newday//-/_}
+printf -v newstruct -- 'Day%02d' "$daynum"
 
 cp -R template "$newday"
 
 sed -i '.bak' -e "s/template/$newday/" "$newday/Cargo.toml"
+sed -i '.bak' -e "s/Template/$newstruct/" -e "s/DAY: u8 = 0/DAY: u8 = $daynum/" "$newday/src/lib.rs"
+sed -i '.bak' -e "s/template::Template/$newcrate::$newstruct/" "$newday/src/main.rs"
 
 printf -v newdayversion -- 's/^version =.*$/version = "0.%d.0"/' "$daynum"
 
-printf -v newtoday -- 's|^path = "day-.*$|path = "%s/src/main.rs"|' "$newday"
+sed -i '.bak' -e "$newdayversion" Cargo.toml
 
-sed -i '.bak' -e "$newdayversion" -e "$newtoday" Cargo.toml
+# link the new day into the `aoc` runner
+perl -0pi -e "s/(.*^day-\\d+ = [^\n]*\n)/\$1$newday = { path = \"$newday\" }\n/ms" Cargo.toml
+perl -pi -e "print qq{    &$newcrate::$newstruct,\n} if m{// new-day.sh adds new days}" src/solutions.rs
 
-rm "Cargo.toml.bak" "$newday/Cargo.toml.bak"
+rm "Cargo.toml.bak" "$newday/Cargo.toml.bak" "$newday/src/lib.rs.bak" "$newday/src/main.rs.bak"
 
-git add "Cargo.toml" "$newday"
+git add "Cargo.toml" "src/solutions.rs" "$newday"
 git commit -am "day-$daynum"
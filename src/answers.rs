@@ -0,0 +1,220 @@
+//! What we know about each answer: the one the site accepted, and the ones it didn't.
+//!
+//! Kept in `answers.json` at the repo root, keyed by day and then part:
+//!
+//! ```json
+//! {
+//!   "7": {
+//!     "1": { "rejected": [{ "value": 249407921, "hint": "too-low" }] }
+//!   }
+//! }
+//! ```
+
+use std::{collections::BTreeMap, fmt, fs::read_to_string, io, path::Path};
+
+use anyhow::{Context, Result};
+use aoc_common::Answer;
+use serde::{Deserialize, Serialize};
+
+use crate::solutions::Part;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Answers(BTreeMap<u8, BTreeMap<Part, Record>>);
+
+/// Everything we've submitted for one part of one day
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Record {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correct: Option<Answer>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rejected: Vec<Rejected>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Rejected {
+    pub value: Answer,
+    /// What the site said about it, if anything
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Hint>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// How an answer measures up against what we know
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Matches,
+    Regressed {
+        expected: Answer,
+    },
+    /// We've submitted exactly this before and it was wrong
+    Rejected,
+    TooHigh {
+        bound: Answer,
+    },
+    TooLow {
+        bound: Answer,
+    },
+    /// Nothing to compare against
+    Unknown,
+}
+
+impl Answers {
+    /// A missing file is an empty store
+    pub fn load(path: &Path) -> Result<Self> {
+        match read_to_string(path) {
+            Ok(s) => {
+                serde_json::from_str(&s).with_context(|| format!("parsing {}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &Answer) -> Verdict {
+        match self.0.get(&day).and_then(|d| d.get(&part)) {
+            Some(record) => record.check(answer),
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+impl Record {
+    pub fn check(&self, answer: &Answer) -> Verdict {
+        if let Some(expected) = &self.correct {
+            return match expected == answer {
+                true => Verdict::Matches,
+                false => Verdict::Regressed {
+                    expected: expected.clone(),
+                },
+            };
+        }
+
+        if self.rejected.iter().any(|r| &r.value == answer) {
+            return Verdict::Rejected;
+        }
+
+        // The tightest bound the answer falls foul of
+        let high = self
+            .bounds(Hint::TooHigh)
+            .filter(|&b| answer >= b)
+            .min_by(|a, b| a.partial_cmp(b).unwrap());
+        if let Some(bound) = high {
+            return Verdict::TooHigh {
+                bound: bound.clone(),
+            };
+        }
+        let low = self
+            .bounds(Hint::TooLow)
+            .filter(|&b| answer <= b)
+            .max_by(|a, b| a.partial_cmp(b).unwrap());
+        if let Some(bound) = low {
+            return Verdict::TooLow {
+                bound: bound.clone(),
+            };
+        }
+
+        Verdict::Unknown
+    }
+
+    /// Rejected values carrying this hint (only numbers can bound anything)
+    fn bounds(&self, hint: Hint) -> impl Iterator<Item = &Answer> {
+        self.rejected
+            .iter()
+            .filter(move |r| r.hint == Some(hint))
+            .map(|r| &r.value)
+            .filter(|v| v.to_bigint().is_some())
+    }
+}
+
+impl Verdict {
+    /// Whether the answer could still be right
+    pub fn is_plausible(&self) -> bool {
+        matches!(self, Self::Matches | Self::Unknown)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Matches => write!(f, "matches"),
+            Self::Regressed { expected } => write!(f, "regressed: expected {expected}"),
+            Self::Rejected => write!(f, "already rejected"),
+            Self::TooHigh { bound } => write!(f, "violates the too-high bound of {bound}"),
+            Self::TooLow { bound } => write!(f, "violates the too-low bound of {bound}"),
+            Self::Unknown => write!(f, "unverified"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Day 7 part 2, as it went on the day
+    const EXAMPLE: &str = r#"{
+        "7": {
+            "2": {
+                "rejected": [
+                    { "value": 248465369, "hint": "too-high" },
+                    { "value": 247687768, "hint": "too-low" },
+                    { "value": 248583384, "hint": "too-high" }
+                ]
+            }
+        },
+        "13": { "1": { "correct": 33975 } }
+    }"#;
+
+    fn example() -> Answers {
+        serde_json::from_str(EXAMPLE).unwrap()
+    }
+
+    #[test]
+    fn correct() {
+        let answers = example();
+        assert_eq!(
+            answers.check(13, Part::One, &33975_usize.into()),
+            Verdict::Matches
+        );
+        assert_eq!(
+            answers.check(13, Part::One, &33976_usize.into()),
+            Verdict::Regressed {
+                expected: 33975_usize.into()
+            }
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        let answers = example();
+        assert_eq!(
+            answers.check(7, Part::Two, &248465369_usize.into()),
+            Verdict::Rejected
+        );
+        assert_eq!(
+            answers.check(7, Part::Two, &248500000_usize.into()),
+            Verdict::TooHigh {
+                bound: 248465369_usize.into()
+            }
+        );
+        assert_eq!(
+            answers.check(7, Part::Two, &1_usize.into()),
+            Verdict::TooLow {
+                bound: 247687768_usize.into()
+            }
+        );
+        assert_eq!(
+            answers.check(7, Part::Two, &248000000_usize.into()),
+            Verdict::Unknown
+        );
+        assert_eq!(
+            answers.check(7, Part::One, &1_usize.into()),
+            Verdict::Unknown
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Timing statistics, and a history of them to compare against.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::{read_to_string, write},
+    io,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Summary of repeated timings of one phase
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    #[serde(with = "nanos")]
+    pub min: Duration,
+    #[serde(with = "nanos")]
+    pub median: Duration,
+    #[serde(with = "nanos")]
+    pub p95: Duration,
+    #[serde(with = "nanos")]
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Panics if there are no samples
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+
+        // nearest-rank percentiles
+        let rank = |p: f64| sorted[((p * n as f64).ceil() as usize).clamp(1, n) - 1];
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = secs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+
+        Self {
+            min: sorted[0],
+            median: rank(0.5),
+            p95: rank(0.95),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+
+    /// How much slower (as a percentage of `before`) this median is
+    pub fn change_from(&self, before: &Stats) -> f64 {
+        let before = before.median.as_secs_f64();
+        let after = self.median.as_secs_f64();
+        100.0 * (after - before) / before
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:>10.2?}  median {:>10.2?}  p95 {:>10.2?}  σ {:>10.2?}",
+            self.min, self.median, self.p95, self.stddev
+        )
+    }
+}
+
+/// One `aoc bench`: for each day, the stats for each phase ("parse", "part 1", "part 2")
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BenchRun {
+    /// Seconds since the epoch
+    pub timestamp: u64,
+    pub days: BTreeMap<u8, BTreeMap<String, Stats>>,
+}
+
+impl BenchRun {
+    pub fn new() -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        Self {
+            timestamp,
+            days: BTreeMap::new(),
+        }
+    }
+}
+
+/// Every bench run so far, oldest first
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History(Vec<BenchRun>);
+
+impl History {
+    /// A missing file is an empty history
+    pub fn load(path: &Path) -> Result<Self> {
+        match read_to_string(path) {
+            Ok(s) => {
+                serde_json::from_str(&s).with_context(|| format!("parsing {}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("writing {}", path.display()))
+    }
+
+    /// The most recent stats for this day and phase
+    pub fn latest(&self, day: u8, phase: &str) -> Option<&Stats> {
+        self.0
+            .iter()
+            .rev()
+            .find_map(|run| run.days.get(&day)?.get(phase))
+    }
+
+    pub fn push(&mut self, run: BenchRun) {
+        self.0.push(run);
+    }
+}
+
+/// (De)serialise a `Duration` as a whole number of nanoseconds
+mod nanos {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(d: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(d.as_nanos().try_into().unwrap_or(u64::MAX))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ms(x: u64) -> Duration {
+        Duration::from_millis(x)
+    }
+
+    #[test]
+    fn stats() {
+        let samples: Vec<Duration> = (1..=20).rev().map(ms).collect();
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(10));
+        assert_eq!(stats.p95, ms(19));
+        // population standard deviation of 1..=20
+        assert_eq!(stats.stddev.as_micros(), 5766);
+    }
+
+    #[test]
+    fn history() {
+        let stats = |x| Stats::from_samples(&[ms(x)]);
+        let mut history = History::default();
+        for (day, x) in [(1, 10), (2, 20), (1, 30)] {
+            let mut run = BenchRun::new();
+            run.days
+                .entry(day)
+                .or_default()
+                .insert("parse".to_string(), stats(x));
+            history.push(run);
+        }
+        let history: History =
+            serde_json::from_str(&serde_json::to_string(&history).unwrap()).unwrap();
+
+        assert_eq!(history.latest(1, "parse"), Some(&stats(30)));
+        assert_eq!(history.latest(2, "parse"), Some(&stats(20)));
+        assert_eq!(history.latest(2, "part 1"), None);
+        assert!((stats(30).change_from(&stats(20)) - 50.0).abs() < 1e-9);
+    }
+}
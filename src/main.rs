@@ -0,0 +1,261 @@
+//! One binary for every day.
+//!
+//! ```text
+//! aoc run --day 5 --part 2 input.txt
+//! aoc run --all
+//! aoc bench --runs 20
+//! ```
+//!
+//! Without an explicit input file, day N reads `day-NN/input.txt`.
+//! Each day's input is parsed once and shared by both parts; the time taken
+//! by each of the three phases is reported separately.
+//! Every answer is checked against what `answers.json` knows about it,
+//! and the run fails if any answer is known to be wrong.
+//!
+//! `aoc bench` times every phase of every day repeatedly, appends the results
+//! to `bench-history.json`, and flags medians that got slower since last time.
+
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+
+mod answers;
+mod bench;
+mod solutions;
+
+use answers::Answers;
+use bench::{BenchRun, History, Stats};
+use solutions::{Part, Runnable, SOLUTIONS};
+
+#[derive(Parser)]
+#[command(name = "aoc")]
+pub struct Opts {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day, or every day, against its puzzle input
+    Run(RunOpts),
+    /// Time every day's parse and parts over many runs
+    Bench(BenchOpts),
+}
+
+#[derive(Args)]
+struct RunOpts {
+    /// Day to run
+    #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+
+    /// Only run this part (default: both)
+    #[arg(long)]
+    part: Option<Part>,
+
+    /// Run every day against its `day-NN/input.txt`
+    #[arg(long)]
+    all: bool,
+
+    /// Puzzle input (default: `day-NN/input.txt`)
+    #[arg(conflicts_with = "all")]
+    infile: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct BenchOpts {
+    /// Only bench this day (default: every day with an input)
+    #[arg(long)]
+    day: Option<u8>,
+
+    /// Times to run each day
+    #[arg(long, default_value_t = 10)]
+    runs: usize,
+
+    /// Flag medians that got slower by more than this percentage
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+
+    /// Where to keep results (default: `bench-history.json`)
+    #[arg(long)]
+    history: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let opts: Opts = clap::Parser::parse();
+
+    match opts.command {
+        Command::Run(run) => run_cmd(&run),
+        Command::Bench(bench) => bench_cmd(&bench),
+    }
+}
+
+fn run_cmd(opts: &RunOpts) -> Result<()> {
+    let answers = Answers::load(&answers_path())?;
+
+    if opts.all {
+        let mut wrong = 0;
+        for solution in SOLUTIONS {
+            let day = solution.day();
+            let path = default_input(day);
+            let Ok(infile) = read_to_string(&path) else {
+                println!("Day {day:02}: no input at {}, skipping", path.display());
+                continue;
+            };
+            println!("Day {day:02}");
+            match run_day(*solution, &infile, opts.part, &answers) {
+                Ok(n) => wrong += n,
+                Err(e) => println!("failed: {e:#}"),
+            }
+            println!();
+        }
+        if wrong > 0 {
+            bail!("{wrong} answer(s) known to be wrong");
+        }
+        return Ok(());
+    }
+
+    let day = opts.day.context("no day given")?;
+    let solution = SOLUTIONS
+        .iter()
+        .find(|s| s.day() == day)
+        .copied()
+        .with_context(|| format!("no solution for day {day}"))?;
+    let path = opts.infile.clone().unwrap_or_else(|| default_input(day));
+    let infile = read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+
+    if run_day(solution, &infile, opts.part, &answers)? > 0 {
+        bail!("answer known to be wrong");
+    }
+    Ok(())
+}
+
+/// Returns how many answers were known to be wrong
+fn run_day(
+    solution: &dyn Runnable,
+    infile: &str,
+    part: Option<Part>,
+    answers: &Answers,
+) -> Result<usize> {
+    let start = Instant::now();
+    let input = solution
+        .parse_any(infile)
+        .inspect_err(aoc_common::report_parse_error)?;
+    println!("Parse: {:.2?}", start.elapsed());
+
+    let mut wrong = 0;
+    for p in [Part::One, Part::Two] {
+        if part.is_none_or(|x| x == p) {
+            let start = Instant::now();
+            let answer = solution.solve_any(p, input.as_ref())?;
+            let elapsed = start.elapsed();
+            let verdict = answers.check(solution.day(), p, &answer);
+            println!("Part {p} ({elapsed:.2?}):\n{answer} ({verdict})");
+            if !verdict.is_plausible() {
+                wrong += 1;
+            }
+        }
+    }
+    Ok(wrong)
+}
+
+fn bench_cmd(opts: &BenchOpts) -> Result<()> {
+    if opts.runs == 0 {
+        bail!("need at least one run");
+    }
+    let history_path = opts
+        .history
+        .clone()
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("bench-history.json"));
+    let mut history = History::load(&history_path)?;
+    let mut run = BenchRun::new();
+    let mut regressions = 0;
+
+    for solution in SOLUTIONS {
+        let day = solution.day();
+        if opts.day.is_some_and(|d| d != day) {
+            continue;
+        }
+        let path = default_input(day);
+        let Ok(infile) = read_to_string(&path) else {
+            println!("Day {day:02}: no input at {}, skipping", path.display());
+            continue;
+        };
+
+        println!("Day {day:02}");
+        let samples = match time_day(*solution, &infile, opts.runs) {
+            Ok(samples) => samples,
+            Err(e) => {
+                println!("failed: {e:#}\n");
+                continue;
+            }
+        };
+        for (phase, samples) in samples {
+            let stats = Stats::from_samples(&samples);
+            print!("{phase:>6}  {stats}");
+            if let Some(before) = history.latest(day, &phase) {
+                let change = stats.change_from(before);
+                print!("  {change:+.1}%");
+                if change > opts.threshold {
+                    print!(" (regression)");
+                    regressions += 1;
+                }
+            }
+            println!();
+            run.days.entry(day).or_default().insert(phase, stats);
+        }
+        println!();
+    }
+
+    history.push(run);
+    history.save(&history_path)?;
+
+    if regressions > 0 {
+        println!(
+            "{regressions} phase(s) slower by more than {}%",
+            opts.threshold
+        );
+    }
+    Ok(())
+}
+
+/// Wall-clock time of each phase, for each of `runs` runs
+fn time_day(
+    solution: &dyn Runnable,
+    infile: &str,
+    runs: usize,
+) -> Result<Vec<(String, Vec<Duration>)>> {
+    let mut parse = vec![];
+    let mut parts = [vec![], vec![]];
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let input = solution.parse_any(infile)?;
+        parse.push(start.elapsed());
+
+        for (p, samples) in [Part::One, Part::Two].into_iter().zip(&mut parts) {
+            let start = Instant::now();
+            solution.solve_any(p, input.as_ref())?;
+            samples.push(start.elapsed());
+        }
+    }
+
+    let [one, two] = parts;
+    Ok(vec![
+        ("parse".to_string(), parse),
+        (format!("part {}", Part::One), one),
+        (format!("part {}", Part::Two), two),
+    ])
+}
+
+fn answers_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.json")
+}
+
+fn default_input(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("day-{day:02}/input.txt"))
+}
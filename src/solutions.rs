@@ -0,0 +1,80 @@
+use std::{any::Any, fmt};
+
+use anyhow::Result;
+use aoc_common::{Answer, Solution};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+pub enum Part {
+    #[value(name = "1")]
+    #[serde(rename = "1")]
+    One,
+    #[value(name = "2")]
+    #[serde(rename = "2")]
+    Two,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::One => write!(f, "1"),
+            Self::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Object-safe view of a [`Solution`], so that every day fits in one list
+pub trait Runnable {
+    fn day(&self) -> u8;
+    fn parse_any(&self, infile: &str) -> Result<Box<dyn Any>>;
+    fn solve_any(&self, part: Part, input: &dyn Any) -> Result<Answer>;
+}
+
+impl<S> Runnable for S
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse_any(&self, infile: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(S::parse(infile)?))
+    }
+
+    /// Panics if `input` didn't come from this solution's `parse_any`
+    fn solve_any(&self, part: Part, input: &dyn Any) -> Result<Answer> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input parsed by a different day");
+        match part {
+            Part::One => S::part_1(input),
+            Part::Two => S::part_2(input),
+        }
+    }
+}
+
+/// Every day we have a solution for, in order
+pub const SOLUTIONS: &[&dyn Runnable] = &[
+    &day_01::Day01,
+    &day_02::Day02,
+    &day_03::Day03,
+    &day_04::Day04,
+    &day_05::Day05,
+    &day_06::Day06,
+    &day_07::Day07,
+    &day_08::Day08,
+    &day_09::Day09,
+    &day_10::Day10,
+    &day_11::Day11,
+    &day_12::Day12,
+    &day_13::Day13,
+    &day_14::Day14,
+    &day_15::Day15,
+    &day_16::Day16,
+    &day_17::Day17,
+    &day_18::Day18,
+    // new-day.sh adds new days above this line
+];
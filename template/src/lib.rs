@@ -0,0 +1,46 @@
+use anyhow::Result;
+use aoc_common::{Answer, Solution};
+
+pub struct Template;
+
+impl Solution for Template {
+    const DAY: u8 = 0;
+    type Input = String;
+
+    fn parse(infile: &str) -> Result<String> {
+        Ok(infile.to_string())
+    }
+    fn part_1(input: &String) -> Result<Answer> {
+        part_1(input).map(Answer::from)
+    }
+    fn part_2(input: &String) -> Result<Answer> {
+        part_2(input).map(Answer::from)
+    }
+}
+
+fn part_1(_infile: &str) -> Result<usize> {
+    todo!()
+}
+fn part_2(_infile: &str) -> Result<usize> {
+    todo!()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r"
+";
+
+    #[test]
+    #[ignore = "fill in the example answer"]
+    fn part_1_example() {
+        assert_eq!(part_1(EXAMPLE_1).unwrap(), 0);
+    }
+
+    #[test]
+    #[ignore = "fill in the example answer"]
+    fn part_2_example() {
+        assert_eq!(part_2(EXAMPLE_1).unwrap(), 0);
+    }
+}